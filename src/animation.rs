@@ -38,6 +38,8 @@ fn animate_sprites(
 #[derive(Clone, Debug, Component)]
 pub enum AnimationIndices {
     Cycle(std::iter::Cycle<std::ops::RangeInclusive<usize>>),
+    /// Same as `Cycle`, but plays the frames back to front
+    ReverseCycle(std::iter::Cycle<std::iter::Rev<std::ops::RangeInclusive<usize>>>),
     OneShot(std::ops::RangeInclusive<usize>),
 }
 impl AnimationIndices {
@@ -48,6 +50,7 @@ impl AnimationIndices {
             // TODO: Create helper functions to instantiate AnimationIndices types, that way it's
             // easier to include metadata
             AnimationIndices::Cycle(cycle) => cycle.clone().next().unwrap_or_default(),
+            AnimationIndices::ReverseCycle(cycle) => cycle.clone().next().unwrap_or_default(),
             AnimationIndices::OneShot(range_inclusive) => *range_inclusive.start(),
         }
     }
@@ -58,6 +61,7 @@ impl Iterator for AnimationIndices {
     fn next(&mut self) -> Option<Self::Item> {
         match self {
             AnimationIndices::Cycle(cycle) => cycle.next(),
+            AnimationIndices::ReverseCycle(cycle) => cycle.next(),
             AnimationIndices::OneShot(range_inclusive) => range_inclusive.next(),
         }
     }
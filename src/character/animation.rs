@@ -12,11 +12,22 @@ pub(super) fn plugin(app: &mut App) {
             .chain()
             .in_set(InGameSystems::Vfx),
     )
-    .insert_resource(DefaultAnimationConfig::default());
+    .insert_resource(DefaultAnimationConfig::default())
+    .insert_resource(BatAnimationConfig::default());
+}
+
+/// Which sprite sheet layout a character's animations are laid out in
+#[derive(Component, Default, PartialEq, Debug, Copy, Clone)]
+pub enum AnimationSheet {
+    /// The 13x21 humanoid sheet shared by the player, humanoid enemies and NPCs
+    #[default]
+    Humanoid,
+    /// 4x4 sheet of wing flaps, one row per direction
+    Bat,
 }
 
 #[derive(Component, Default, PartialEq, Debug, Hash, Eq, Copy, Clone)]
-#[require(FacingDirection, SimpleMotion)]
+#[require(FacingDirection, SimpleMotion, AnimationSheet)]
 pub enum CharacterAnimationState {
     #[default]
     Idle,
@@ -29,7 +40,7 @@ fn update_animation_state(
     mut character_query: Query<
         (
             &SimpleMotion,
-            &AttackState,
+            Ref<AttackState>,
             Option<&Health>,
             &mut CharacterAnimationState,
             &mut FacingDirection,
@@ -52,7 +63,10 @@ fn update_animation_state(
 
         // Attacking animation takes priority over walking / idle
         if attack_state.is_attacking {
-            animation_state.set_if_neq(CharacterAnimationState::Attacking);
+            if attack_state.is_changed() {
+                // Each step of a combo restarts the attack animation
+                *animation_state = CharacterAnimationState::Attacking;
+            }
             continue;
         }
 
@@ -65,7 +79,8 @@ fn update_animation_state(
 }
 
 fn cycle_character_animation(
-    animation_config: Res<DefaultAnimationConfig>,
+    humanoid_config: Res<DefaultAnimationConfig>,
+    bat_config: Res<BatAnimationConfig>,
     mut query: Query<
         (
             &mut AnimationIndices,
@@ -73,12 +88,26 @@ fn cycle_character_animation(
             &mut Sprite,
             &CharacterAnimationState,
             &FacingDirection,
+            &AnimationSheet,
+            Option<&AttackState>,
         ),
         Or<(Changed<CharacterAnimationState>, Changed<FacingDirection>)>,
     >,
 ) {
-    for (mut indices, mut timer, mut sprite, state, direction) in &mut query {
-        *indices = animation_config.get_indices(*state, *direction);
+    for (mut indices, mut timer, mut sprite, state, direction, sheet, attack_state) in &mut query {
+        let animation_config = match sheet {
+            AnimationSheet::Humanoid => &*humanoid_config,
+            AnimationSheet::Bat => &bat_config.0,
+        };
+        *indices = match (state, attack_state) {
+            // Every other step of a combo is a backswing, so play the swing the other way
+            (CharacterAnimationState::Attacking, Some(attack_state))
+                if attack_state.combo_step % 2 == 1 =>
+            {
+                animation_config.get_reversed_indices(*state, *direction)
+            }
+            _ => animation_config.get_indices(*state, *direction),
+        };
         *timer = AnimationTimer(animation_config.get_timer(*state, *direction));
         if let Some(atlas) = &mut sprite.texture_atlas {
             atlas.index = indices.start();
@@ -125,6 +154,44 @@ impl Default for DefaultAnimationConfig {
     }
 }
 
+/// Bats flap the same wing cycle whatever they're doing, just faster when moving or diving. They
+/// have no death frames so they hold a single frame while falling.
+#[derive(Resource, Deref)]
+pub struct BatAnimationConfig(pub DefaultAnimationConfig);
+
+impl Default for BatAnimationConfig {
+    fn default() -> Self {
+        use CharacterAnimationState::{Attacking, Dying, Idle, Moving};
+        use FacingDirection::{Down, Left, Right, Up};
+        let data = [
+            (Idle, Down, (0, 4, 0.15)),
+            (Idle, Right, (1, 4, 0.15)),
+            (Idle, Up, (2, 4, 0.15)),
+            (Idle, Left, (3, 4, 0.15)),
+            (Moving, Down, (0, 4, 0.08)),
+            (Moving, Right, (1, 4, 0.08)),
+            (Moving, Up, (2, 4, 0.08)),
+            (Moving, Left, (3, 4, 0.08)),
+            (Attacking, Down, (0, 4, 0.05)),
+            (Attacking, Right, (1, 4, 0.05)),
+            (Attacking, Up, (2, 4, 0.05)),
+            (Attacking, Left, (3, 4, 0.05)),
+            (Dying, Down, (0, 1, 1.0)),
+            (Dying, Right, (1, 1, 1.0)),
+            (Dying, Up, (2, 1, 1.0)),
+            (Dying, Left, (3, 1, 1.0)),
+        ];
+        let animations = data
+            .into_iter()
+            .map(|(state, dir, data)| ((state, dir), AnimationData::from(data)))
+            .collect::<HashMap<_, _>>();
+        Self(DefaultAnimationConfig {
+            columns: 4,
+            animations,
+        })
+    }
+}
+
 impl DefaultAnimationConfig {
     pub fn get_animation(
         &self,
@@ -151,6 +218,17 @@ impl DefaultAnimationConfig {
         AnimationIndices::Cycle((first..=last).cycle())
     }
 
+    pub fn get_reversed_indices(
+        &self,
+        state: CharacterAnimationState,
+        direction: FacingDirection,
+    ) -> AnimationIndices {
+        let animation = self.get_animation(state, direction);
+        let first = animation.row * self.columns;
+        let last = first + animation.frame_count - 1;
+        AnimationIndices::ReverseCycle((first..=last).rev().cycle())
+    }
+
     pub fn get_timer(&self, state: CharacterAnimationState, direction: FacingDirection) -> Timer {
         let animation = self.get_animation(state, direction);
         Timer::from_seconds(animation.frame_duration, TimerMode::Repeating)
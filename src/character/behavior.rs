@@ -1,11 +1,15 @@
 use bevy_behave::prelude::{BehaveCtx, BehaveTrigger};
 use rand::{Rng, rng};
-use std::ops::Range;
+use std::{f32::consts::FRAC_PI_4, ops::Range};
 
 use bevy::prelude::*;
 
 use crate::{
-    character::vision::{TargetInfo, Targeting},
+    character::{
+        marksmanship::AimSolution,
+        pack::{FLANK_ENGAGE_DISTANCE, FlankOffset, HoldBack},
+        vision::{TargetInfo, Targeting},
+    },
     prelude::*,
 };
 
@@ -17,13 +21,38 @@ pub(super) fn plugin(app: &mut App) {
             while_idling,
             while_wandering,
             while_retreating,
+            while_following,
             while_keeping_distance_and_firing,
+            while_raising_shield,
+            while_searching,
+            while_fluttering,
+            while_diving,
         )
             .in_set(InGameSystems::Simulation),
     )
     .add_observer(on_idle_start)
     .add_observer(on_wander_start)
-    .add_observer(on_attempt_melee);
+    .add_observer(on_attempt_melee)
+    .add_observer(on_dash_away)
+    .add_observer(on_raise_shield_start)
+    .add_observer(on_raise_shield_end)
+    .add_observer(on_search_start)
+    .add_observer(on_dive_end)
+    .add_observer(on_staggered_interrupt);
+}
+
+/// Fails whatever behavior the staggered character was running, so their tree starts over once
+/// the stagger wears off
+fn on_staggered_interrupt(
+    staggered: On<Staggered>,
+    mut commands: Commands,
+    behavior_query: Query<&BehaveCtx>,
+) {
+    for ctx in &behavior_query {
+        if ctx.target_entity() == staggered.entity {
+            commands.trigger(ctx.failure());
+        }
+    }
 }
 
 #[derive(Component, Clone)]
@@ -63,12 +92,16 @@ pub fn while_idling(
     mut commands: Commands,
     time: Res<Time>,
     mut idle_query: Query<(&BehaveCtx, &mut Idle)>,
-    target_query: Query<&Targeting>,
+    target_query: Query<(Has<Targeting>, Option<&TargetInfo>)>,
 ) {
     idle_query.iter_mut().for_each(|(ctx, mut idle)| {
-        if target_query.get(ctx.target_entity()).is_ok() {
+        let (has_target, target_info) = target_query.get(ctx.target_entity()).unwrap_or_default();
+        if has_target {
             info!("{} Got target while idling", ctx.target_entity());
             commands.trigger(ctx.failure());
+        } else if target_info.is_some_and(|info| info.last_known_position.is_some()) {
+            debug!("{} heard something while idling", ctx.target_entity());
+            commands.trigger(ctx.failure());
         } else if idle.timer.tick(time.delta()).just_finished() {
             commands.trigger(ctx.success());
         }
@@ -87,6 +120,14 @@ impl Anchor {
         Self { origin, distance }
     }
 
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    pub fn radius(&self) -> f32 {
+        self.distance
+    }
+
     pub fn distance_from(&self, transform: &Transform) -> f32 {
         self.origin.distance(transform.translation.xy())
     }
@@ -128,6 +169,7 @@ pub fn on_wander_start(
     mut commands: Commands,
     wander_query: Query<&BehaveCtx, With<Wander>>,
     mut target_query: Query<(&mut SimpleMotion, Option<&Anchor>, &Transform)>,
+    navigation: Navigation,
 ) -> Result {
     let ctx = wander_query.get(wander.entity)?;
     let (mut motion, anchor, transform) = target_query.get_mut(ctx.target_entity())?;
@@ -135,7 +177,10 @@ pub fn on_wander_start(
     if anchor.is_some_and(|a| a.outside_range(transform)) {
         commands.trigger(ctx.failure());
     } else {
-        motion.start_moving(random_direction());
+        // Don't wander face first into a wall
+        motion.start_moving(
+            navigation.open_direction(transform.translation.xy(), random_direction()),
+        );
     }
     Ok(())
 }
@@ -144,37 +189,106 @@ pub fn while_wandering(
     time: Res<Time>,
     mut commands: Commands,
     mut wander_query: Query<(&BehaveCtx, &mut Wander)>,
-    target_query: Query<&Targeting>,
+    target_query: Query<(Has<Targeting>, Option<&TargetInfo>)>,
 ) {
     wander_query.iter_mut().for_each(|(ctx, mut wander)| {
-        if target_query.get(ctx.target_entity()).is_ok() {
+        let (has_target, target_info) = target_query.get(ctx.target_entity()).unwrap_or_default();
+        if has_target {
             info!("{} Got target while wandering", ctx.target_entity());
             commands.trigger(ctx.failure());
+        } else if target_info.is_some_and(|info| info.last_known_position.is_some()) {
+            debug!("{} heard something while wandering", ctx.target_entity());
+            commands.trigger(ctx.failure());
         } else if wander.timer.tick(time.delta()).just_finished() {
             commands.trigger(ctx.success());
         }
     });
 }
 
-/// When a character is not agroed and too far from home, return to origin
+/// Followers start catching up once the player is further than this
+const FOLLOW_DISTANCE: f32 = 96.0;
+/// and stop again once they are this close, so they don't crowd the player
+const FOLLOW_STOP_DISTANCE: f32 = 56.0;
+
+/// Sticks close to the player, catching up whenever left behind. Fails as soon as a target is
+/// found, never succeeds.
+#[derive(Component, Clone)]
+pub struct Follow;
+
+pub fn while_following(
+    mut commands: Commands,
+    follow_query: Query<&BehaveCtx, With<Follow>>,
+    mut target_query: Query<
+        (&mut SimpleMotion, &mut NavPath, &Transform, Has<Targeting>),
+        Without<Player>,
+    >,
+    player: Option<Single<&Transform, With<Player>>>,
+    navigation: Navigation,
+) -> Result {
+    follow_query.iter().try_for_each(|ctx| {
+        let (mut motion, mut nav_path, transform, has_target) =
+            target_query.get_mut(ctx.target_entity())?;
+
+        if has_target {
+            debug!("{} stopped following to fight", ctx.target_entity());
+            commands.trigger(ctx.failure());
+            return Ok(());
+        }
+
+        let Some(player) = &player else {
+            motion.stop_moving();
+            return Ok(());
+        };
+
+        let position = transform.translation.xy();
+        let distance = position.distance(player.translation.xy());
+
+        if distance > FOLLOW_DISTANCE || (motion.is_moving() && distance > FOLLOW_STOP_DISTANCE) {
+            motion.start_moving(navigation.direction_to_player(
+                position,
+                player.translation.xy(),
+                &mut nav_path,
+            ));
+        } else {
+            motion.stop_moving();
+        }
+        Ok(())
+    })
+}
+
+/// When a character is not agroed and too far from home, return to origin. Fails while there is a
+/// last known position to search, so investigating comes first.
 #[derive(Component, Clone)]
 pub struct Retreat;
 
 pub fn while_retreating(
     mut commands: Commands,
     mut retreat_query: Query<&BehaveCtx, With<Retreat>>,
-    mut target_query: Query<(&mut SimpleMotion, &Transform, &Anchor, Has<Targeting>)>,
+    mut target_query: Query<(
+        &mut SimpleMotion,
+        &mut NavPath,
+        &Transform,
+        &Anchor,
+        Has<Targeting>,
+        Option<&TargetInfo>,
+    )>,
+    navigation: Navigation,
 ) -> Result {
     retreat_query.iter_mut().try_for_each(|ctx| {
-        let (mut motion, transform, anchor, has_target) =
+        let (mut motion, mut nav_path, transform, anchor, has_target, target_info) =
             target_query.get_mut(ctx.target_entity())?;
         if has_target {
             commands.trigger(ctx.failure());
+        } else if target_info.is_some_and(|info| info.last_known_position.is_some()) {
+            // Something to go look at, let the tree get back around to searching rather than
+            // walking home first
+            commands.trigger(ctx.failure());
             // within half a tile, we can stop retreating
         } else if anchor.distance_from(transform) < 16.0 {
             commands.trigger(ctx.success());
         } else {
-            let direction = (anchor.origin - transform.translation.xy()).normalize_or_zero();
+            let direction =
+                navigation.direction_to(transform.translation.xy(), anchor.origin, &mut nav_path);
             motion.start_moving(direction);
         }
         Ok(())
@@ -188,13 +302,43 @@ pub struct Chase;
 pub fn while_chasing(
     mut commands: Commands,
     mut chase_query: Query<&BehaveCtx, With<Chase>>,
-    mut target_query: Query<(&mut SimpleMotion, &TargetInfo, Has<Targeting>)>,
+    mut target_query: Query<(
+        &mut SimpleMotion,
+        &mut NavPath,
+        &Transform,
+        &TargetInfo,
+        Option<&Targeting>,
+        Option<&FlankOffset>,
+    )>,
+    player_query: Query<(), With<Player>>,
+    navigation: Navigation,
 ) -> Result {
     chase_query.iter_mut().try_for_each(|ctx| {
-        let (mut motion, target_info, has_target) = target_query.get_mut(ctx.target_entity())?;
+        let (mut motion, mut nav_path, transform, target_info, targeting, flank_offset) =
+            target_query.get_mut(ctx.target_entity())?;
 
-        if has_target {
-            motion.start_moving(target_info.direction);
+        if let Some(targeting) = targeting {
+            let direction = match flank_offset {
+                // Come at the target from our side of the pack until we're close
+                Some(flank_offset) if target_info.distance > FLANK_ENGAGE_DISTANCE => {
+                    let position = transform.translation.xy();
+                    let target_position = position + target_info.direction * target_info.distance;
+                    navigation.direction_to(
+                        position,
+                        target_position + flank_offset.0,
+                        &mut nav_path,
+                    )
+                }
+                _ => direction_to_target(
+                    &navigation,
+                    &mut nav_path,
+                    transform,
+                    target_info,
+                    targeting,
+                    &player_query,
+                ),
+            };
+            motion.start_moving(direction);
 
             if target_info.distance < 64.0 {
                 commands.trigger(ctx.success());
@@ -207,6 +351,127 @@ pub fn while_chasing(
     })
 }
 
+/// Finds a way around walls to whoever the character is targeting. Everyone heading for the player
+/// shares a flow field, other targets get their own cached path.
+fn direction_to_target(
+    navigation: &Navigation,
+    nav_path: &mut NavPath,
+    transform: &Transform,
+    target_info: &TargetInfo,
+    targeting: &Targeting,
+    player_query: &Query<(), With<Player>>,
+) -> Vec2 {
+    let position = transform.translation.xy();
+    let target_position = position + target_info.direction * target_info.distance;
+
+    if player_query.contains(targeting.0) {
+        navigation.direction_to_player(position, target_position, nav_path)
+    } else {
+        navigation.direction_to(position, target_position, nav_path)
+    }
+}
+
+/// Close enough to a search point to count as having checked it
+const SEARCH_POINT_REACHED_DISTANCE: f32 = 24.0;
+/// How far from the last known position a searching character looks around
+const SEARCH_RADIUS: f32 = 96.0;
+/// Gives up on a search point after this long, it may be behind a wall
+const SEARCH_POINT_SECS: f32 = 2.0;
+
+/// Goes to where the target was last seen and looks around for a while. Fails right away if there
+/// is nowhere to search, or as soon as a target is found. Succeeds once the search is given up.
+#[derive(Component, Clone)]
+pub struct Search {
+    duration: Timer,
+    point_timer: Timer,
+    /// Where we are currently headed, the last known position first then random nearby spots
+    point: Option<Vec2>,
+}
+
+impl Search {
+    pub fn new(duration_secs: f32) -> Self {
+        Self {
+            duration: Timer::from_seconds(duration_secs, TimerMode::Once),
+            point_timer: Timer::from_seconds(SEARCH_POINT_SECS, TimerMode::Once),
+            point: None,
+        }
+    }
+}
+
+pub fn on_search_start(
+    search: On<Add, Search>,
+    mut commands: Commands,
+    mut search_query: Query<(&BehaveCtx, &mut Search)>,
+    target_query: Query<(&TargetInfo, Has<Targeting>)>,
+) -> Result {
+    let (ctx, mut search) = search_query.get_mut(search.entity)?;
+    let (target_info, has_target) = target_query.get(ctx.target_entity())?;
+
+    match target_info.last_known_position {
+        Some(last_known_position) if !has_target => {
+            search.point = Some(last_known_position);
+        }
+        _ => commands.trigger(ctx.failure()),
+    }
+
+    Ok(())
+}
+
+pub fn while_searching(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut search_query: Query<(&BehaveCtx, &mut Search)>,
+    mut target_query: Query<(
+        &mut SimpleMotion,
+        &mut NavPath,
+        &Transform,
+        &mut TargetInfo,
+        Has<Targeting>,
+    )>,
+    navigation: Navigation,
+) -> Result {
+    search_query.iter_mut().try_for_each(|(ctx, mut search)| {
+        let (mut motion, mut nav_path, transform, mut target_info, has_target) =
+            target_query.get_mut(ctx.target_entity())?;
+
+        if has_target {
+            debug!("{} found its target while searching", ctx.target_entity());
+            commands.trigger(ctx.failure());
+            return Ok(());
+        }
+
+        let (Some(last_known_position), Some(point)) =
+            (target_info.last_known_position, search.point)
+        else {
+            commands.trigger(ctx.failure());
+            return Ok(());
+        };
+
+        if search.duration.tick(time.delta()).is_finished() {
+            debug!("{} gave up searching", ctx.target_entity());
+            target_info.last_known_position = None;
+            motion.stop_moving();
+            commands.trigger(ctx.success());
+            return Ok(());
+        }
+
+        let position = transform.translation.xy();
+        let reached = position.distance(point) < SEARCH_POINT_REACHED_DISTANCE;
+
+        if reached || search.point_timer.tick(time.delta()).is_finished() {
+            // Look around somewhere else near where the target was last seen
+            search.point = Some(
+                last_known_position + random_direction() * rng().random_range(0.0..SEARCH_RADIUS),
+            );
+            search.point_timer.reset();
+        } else {
+            motion.start_moving(navigation.direction_to(position, point, &mut nav_path));
+        }
+
+        Ok(())
+    })
+}
+
 fn random_direction() -> Vec2 {
     let mut rng = rng();
     let angle = rng.random_range(0.0..std::f32::consts::TAU);
@@ -240,6 +505,76 @@ pub fn on_attempt_melee(
     Ok(())
 }
 
+/// Holds up the offhand shield for a moment. Succeeds right away for characters without one.
+#[derive(Component, Clone)]
+pub struct RaiseShield {
+    timer: Timer,
+}
+
+impl RaiseShield {
+    pub fn new(duration_secs: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration_secs, TimerMode::Once),
+        }
+    }
+}
+
+pub fn on_raise_shield_start(
+    raise_shield: On<Add, RaiseShield>,
+    mut commands: Commands,
+    raise_shield_query: Query<&BehaveCtx, With<RaiseShield>>,
+    target_query: Query<Option<&Offhand>>,
+    shield_query: Query<(), With<Shield>>,
+) -> Result {
+    let ctx = raise_shield_query.get(raise_shield.entity)?;
+
+    match target_query.get(ctx.target_entity())? {
+        Some(offhand) if shield_query.contains(offhand.get()) => {
+            commands.trigger(AIUseEquipment {
+                entity: offhand.get(),
+            });
+        }
+        _ => commands.trigger(ctx.success()),
+    }
+
+    Ok(())
+}
+
+pub fn while_raising_shield(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut raise_shield_query: Query<(&BehaveCtx, &mut RaiseShield)>,
+) {
+    for (ctx, mut raise_shield) in &mut raise_shield_query {
+        if raise_shield.timer.tick(time.delta()).just_finished() {
+            commands.trigger(ctx.success());
+        }
+    }
+}
+
+/// Lowers the shield however the behavior ended, including being interrupted
+pub fn on_raise_shield_end(
+    raise_shield: On<Remove, RaiseShield>,
+    mut commands: Commands,
+    raise_shield_query: Query<&BehaveCtx, With<RaiseShield>>,
+    target_query: Query<&Offhand>,
+    active_shield_query: Query<(), With<ActiveShield>>,
+) {
+    if let Ok(ctx) = raise_shield_query.get(raise_shield.entity)
+        && let Ok(offhand) = target_query.get(ctx.target_entity())
+        && active_shield_query.contains(offhand.get())
+    {
+        commands.trigger(StopUsingEquipment {
+            entity: offhand.get(),
+        });
+    }
+}
+
+/// Targets closer than this are considered "too close" by ranged characters that can dash
+const DASH_AWAY_DISTANCE: f32 = 100.0;
+
+/// Fires at the target while staying at range, holding fire until there is a clear shot. Succeeds
+/// if the target gets too close and the character is able to dash away.
 #[derive(Component, Clone)]
 pub struct KeepDistanceAndFire;
 
@@ -248,27 +583,85 @@ pub fn while_keeping_distance_and_firing(
     mut behave_query: Query<&BehaveCtx, With<KeepDistanceAndFire>>,
     mut target_query: Query<(
         &mut SimpleMotion,
+        &mut NavPath,
+        &Transform,
         &TargetInfo,
         Option<&Mainhand>,
-        Has<Targeting>,
+        Option<&Dash>,
+        Option<&Targeting>,
+        Has<HoldBack>,
+        Option<&AimSolution>,
     )>,
+    player_query: Query<(), With<Player>>,
+    navigation: Navigation,
 ) -> Result {
     behave_query.iter_mut().try_for_each(|ctx| {
-        let (mut motion, target_info, mainhand, has_target) =
-            target_query.get_mut(ctx.target_entity())?;
+        let (
+            mut motion,
+            mut nav_path,
+            transform,
+            target_info,
+            mainhand,
+            dash,
+            targeting,
+            hold_back,
+            aim,
+        ) = target_query.get_mut(ctx.target_entity())?;
+
+        // Stay out of the way while melee allies are on the target
+        let (min_range, max_range) = if hold_back {
+            (260.0, 340.0)
+        } else {
+            (200.0, 300.0)
+        };
 
-        if !has_target {
+        let Some(targeting) = targeting else {
             commands.trigger(ctx.failure());
+            return Ok(());
+        };
+
+        if target_info.distance < DASH_AWAY_DISTANCE && dash.is_some_and(Dash::is_ready) {
+            // Target is in our face, let the tree decide if we should get away from them
+            commands.trigger(ctx.success());
         } else if let Some(mainhand) = mainhand {
-            commands.trigger(AIUseEquipment {
-                entity: mainhand.get(),
-            });
+            let clear_shot = aim.map_or(target_info.line_of_sight, |aim| aim.clear_shot);
+
+            if aim.map_or(clear_shot, |aim| aim.ready) {
+                commands.trigger(AIUseEquipment {
+                    entity: mainhand.get(),
+                });
+            }
 
-            if target_info.distance < 200.0 {
-                // If target is too close we try to move away
-                motion.start_moving(-target_info.direction);
-            } else if target_info.distance > 300.0 {
-                motion.start_moving(target_info.direction);
+            if !target_info.line_of_sight {
+                // Can't see them, find a way around whatever is in between
+                motion.start_moving(direction_to_target(
+                    &navigation,
+                    &mut nav_path,
+                    transform,
+                    target_info,
+                    targeting,
+                    &player_query,
+                ));
+            } else if !clear_shot {
+                // An ally is in the line of fire, sidestep for a clean shot
+                motion.start_moving(
+                    navigation
+                        .open_direction(transform.translation.xy(), target_info.direction.perp()),
+                );
+            } else if target_info.distance < min_range {
+                // If target is too close we try to move away, without backing into a wall
+                motion.start_moving(
+                    navigation.open_direction(transform.translation.xy(), -target_info.direction),
+                );
+            } else if target_info.distance > max_range {
+                motion.start_moving(direction_to_target(
+                    &navigation,
+                    &mut nav_path,
+                    transform,
+                    target_info,
+                    targeting,
+                    &player_query,
+                ));
             }
         } else {
             // No mainhand equipped
@@ -277,3 +670,196 @@ pub fn while_keeping_distance_and_firing(
         Ok(())
     })
 }
+
+/// Dashes away from the current target, veering off to a random side
+#[derive(Clone)]
+pub struct DashAway;
+
+pub fn on_dash_away(
+    dash_away: On<BehaveTrigger<DashAway>>,
+    mut commands: Commands,
+    target_query: Query<(&TargetInfo, &Dash, Has<Targeting>)>,
+) -> Result {
+    let ctx = dash_away.ctx();
+
+    let (target_info, dash, has_target) = target_query.get(ctx.target_entity())?;
+
+    if has_target && dash.is_ready() {
+        let angle = rng().random_range(-FRAC_PI_4..FRAC_PI_4);
+        commands.trigger(AttemptDash {
+            entity: ctx.target_entity(),
+            direction: Vec2::from_angle(angle).rotate(-target_info.direction),
+        });
+        commands.trigger(ctx.success());
+    } else {
+        commands.trigger(ctx.failure());
+    }
+
+    Ok(())
+}
+
+/// Flits around in short bursts, picking a new direction every fraction of a second. Fails as soon
+/// as a target is found or the character strays too far from home.
+#[derive(Component, Clone)]
+pub struct Flutter {
+    duration: Timer,
+    turn_timer: Timer,
+}
+
+impl Flutter {
+    pub fn timer_range(duration_range: Range<f32>) -> Self {
+        let mut rng = rng();
+        Self {
+            duration: Timer::from_seconds(rng.random_range(duration_range), TimerMode::Once),
+            turn_timer: Timer::from_seconds(FLUTTER_TURN_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+/// How often a fluttering or diving character changes direction
+const FLUTTER_TURN_SECS: f32 = 0.25;
+
+pub fn while_fluttering(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flutter_query: Query<(&BehaveCtx, &mut Flutter)>,
+    mut target_query: Query<(
+        &mut SimpleMotion,
+        &Transform,
+        Option<&Anchor>,
+        Has<Targeting>,
+        Option<&TargetInfo>,
+    )>,
+    navigation: Navigation,
+) -> Result {
+    flutter_query.iter_mut().try_for_each(|(ctx, mut flutter)| {
+        let (mut motion, transform, anchor, has_target, target_info) =
+            target_query.get_mut(ctx.target_entity())?;
+
+        if has_target || anchor.is_some_and(|a| a.outside_range(transform)) {
+            commands.trigger(ctx.failure());
+        } else if target_info.is_some_and(|info| info.last_known_position.is_some()) {
+            debug!("{} heard something while fluttering", ctx.target_entity());
+            motion.stop_moving();
+            commands.trigger(ctx.failure());
+        } else if flutter.duration.tick(time.delta()).is_finished() {
+            motion.stop_moving();
+            commands.trigger(ctx.success());
+        } else if flutter.turn_timer.tick(time.delta()).just_finished() || !motion.is_moving() {
+            motion.start_moving(
+                navigation.open_direction(transform.translation.xy(), random_direction()),
+            );
+        }
+
+        Ok(())
+    })
+}
+
+/// Gets within this distance of the target before winding up a dive
+const DIVE_RANGE: f32 = 140.0;
+/// How far past the target a dive carries on
+const DIVE_OVERSHOOT: f32 = 48.0;
+const DIVE_SECS: f32 = 0.3;
+const DIVE_WINDUP_SECS: f32 = 0.35;
+const DIVE_RECOVER_SECS: f32 = 0.6;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DivePhase {
+    Approach,
+    Windup,
+    Diving,
+    Recover,
+}
+
+/// Zig-zags towards the target, hangs in the air for a moment, then swoops straight through them.
+/// Damage comes from the character's contact hitbox while the swoop lasts. Succeeds after
+/// recovering from the swoop, fails if the target is lost.
+#[derive(Component, Clone)]
+pub struct Dive {
+    phase: DivePhase,
+    timer: Timer,
+}
+
+impl Default for Dive {
+    fn default() -> Self {
+        Self {
+            phase: DivePhase::Approach,
+            timer: Timer::from_seconds(FLUTTER_TURN_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+pub fn while_diving(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut dive_query: Query<(&BehaveCtx, &mut Dive)>,
+    mut target_query: Query<(
+        &mut SimpleMotion,
+        &mut AttackState,
+        &TargetInfo,
+        Has<Targeting>,
+    )>,
+) -> Result {
+    dive_query.iter_mut().try_for_each(|(ctx, mut dive)| {
+        let (mut motion, mut attack_state, target_info, has_target) =
+            target_query.get_mut(ctx.target_entity())?;
+
+        // Once committed to the swoop there's no stopping it
+        if !has_target && matches!(dive.phase, DivePhase::Approach | DivePhase::Windup) {
+            motion.stop_moving();
+            commands.trigger(ctx.failure());
+            return Ok(());
+        }
+
+        let finished = dive.timer.tick(time.delta()).just_finished();
+
+        match dive.phase {
+            DivePhase::Approach => {
+                if target_info.distance <= DIVE_RANGE && target_info.line_of_sight {
+                    motion.stop_moving();
+                    // Face the target during the windup without drifting towards it
+                    motion.direction = target_info.direction;
+                    attack_state.is_attacking = true;
+                    dive.phase = DivePhase::Windup;
+                    dive.timer = Timer::from_seconds(DIVE_WINDUP_SECS, TimerMode::Once);
+                } else if finished || !motion.is_moving() {
+                    // Erratic, but always drifting closer
+                    let direction = (target_info.direction + random_direction() * 0.8)
+                        .normalize_or(target_info.direction);
+                    motion.start_moving(direction);
+                }
+            }
+            DivePhase::Windup if finished => {
+                commands.entity(ctx.target_entity()).insert(Dashing::new(
+                    target_info.direction,
+                    target_info.distance + DIVE_OVERSHOOT,
+                    DIVE_SECS,
+                ));
+                dive.phase = DivePhase::Diving;
+                dive.timer = Timer::from_seconds(DIVE_SECS, TimerMode::Once);
+            }
+            DivePhase::Diving if finished => {
+                attack_state.is_attacking = false;
+                dive.phase = DivePhase::Recover;
+                dive.timer = Timer::from_seconds(DIVE_RECOVER_SECS, TimerMode::Once);
+            }
+            DivePhase::Recover if finished => commands.trigger(ctx.success()),
+            _ => {}
+        }
+
+        Ok(())
+    })
+}
+
+/// Puts the wings down however the dive ended, including being interrupted
+pub fn on_dive_end(
+    dive: On<Remove, Dive>,
+    dive_query: Query<&BehaveCtx, With<Dive>>,
+    mut target_query: Query<&mut AttackState>,
+) {
+    if let Ok(ctx) = dive_query.get(dive.entity)
+        && let Ok(mut attack_state) = target_query.get_mut(ctx.target_entity())
+    {
+        attack_state.is_attacking = false;
+    }
+}
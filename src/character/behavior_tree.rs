@@ -0,0 +1,329 @@
+use std::fmt;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    ecs::system::SystemParam,
+    prelude::*,
+    scene::ron::{self, de::from_bytes},
+};
+use bevy_behave::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    character::behavior::{
+        AttemptMelee, Chase, DashAway, Dive, Flutter, Follow, Idle, KeepDistanceAndFire,
+        RaiseShield, Retreat, Search, Wander,
+    },
+    prelude::BehaviorTrees,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<BehaviorTreeAsset>()
+        .register_asset_loader(BehaviorTreeLoader);
+}
+
+/// A behavior tree authored in a `.behavior.ron` file under `assets/config/behaviors`. Trees are
+/// built fresh for every spawn, so with hot reloading edits apply to the next character spawned
+#[derive(Asset, TypePath, Debug)]
+pub struct BehaviorTreeAsset {
+    root: BehaviorNode,
+}
+
+impl BehaviorTreeAsset {
+    pub fn tree(&self) -> Tree<Behave> {
+        self.root.tree(&mut 0)
+    }
+
+    /// Every node as `(depth, label)` in depth-first order, matching the indices in
+    /// `RunningBehaviorNode`
+    pub fn outline(&self) -> Vec<(usize, &'static str)> {
+        let mut lines = Vec::new();
+        self.root.outline(0, &mut lines);
+        lines
+    }
+}
+
+/// The asset an entity's behavior tree was built from
+#[derive(Component, Clone)]
+pub struct BehaviorSource(pub Handle<BehaviorTreeAsset>);
+
+/// Added to the entities a tree spawns for its running nodes, indexes into
+/// `BehaviorTreeAsset::outline`
+#[derive(Component, Clone, Copy)]
+pub struct RunningBehaviorNode(pub usize);
+
+/// Builds behavior trees from their loaded assets
+#[derive(SystemParam)]
+pub struct Behaviors<'w> {
+    pub trees: Res<'w, BehaviorTrees>,
+    tree_assets: Res<'w, Assets<BehaviorTreeAsset>>,
+}
+
+impl Behaviors<'_> {
+    /// Builds the tree behind `handle`, falling back to standing idle if the asset isn't loaded
+    pub fn tree(&self, handle: &Handle<BehaviorTreeAsset>) -> Tree<Behave> {
+        if let Some(asset) = self.tree_assets.get(handle) {
+            asset.tree()
+        } else {
+            warn!(
+                "Behavior tree {:?} is not loaded, standing idle",
+                handle.path()
+            );
+            behave! {
+                Behave::Forever => {
+                    Behave::spawn_named("Idle", Idle::default())
+                }
+            }
+        }
+    }
+}
+
+/// Node names map one to one onto the behaviors in `behavior.rs`, durations are in seconds
+#[derive(Deserialize, Debug)]
+enum BehaviorNode {
+    /// Restarts its child every time it finishes
+    Forever(Box<BehaviorNode>),
+    /// Runs children in order until one fails
+    Sequence(Vec<BehaviorNode>),
+    /// Runs children in order until one succeeds
+    Fallback(Vec<BehaviorNode>),
+    /// Keeps running the second node for as long as the first one hasn't finished
+    While(Box<BehaviorNode>, Box<BehaviorNode>),
+    Idle {
+        duration: (f32, f32),
+    },
+    Wander {
+        duration: (f32, f32),
+    },
+    Retreat,
+    Follow,
+    Chase,
+    KeepDistanceAndFire,
+    RaiseShield {
+        duration: f32,
+    },
+    Search {
+        duration: f32,
+    },
+    Flutter {
+        duration: (f32, f32),
+    },
+    Dive,
+    AttemptMelee,
+    DashAway,
+}
+
+impl BehaviorNode {
+    /// `index` counts nodes depth-first so running nodes can be matched back up with the outline
+    fn tree(&self, index: &mut usize) -> Tree<Behave> {
+        let label = self.label();
+        let running = RunningBehaviorNode(*index);
+        *index += 1;
+
+        match self {
+            BehaviorNode::Forever(_) => self.control(Behave::Forever, index),
+            BehaviorNode::Sequence(_) => self.control(Behave::Sequence, index),
+            BehaviorNode::Fallback(_) => self.control(Behave::Fallback, index),
+            BehaviorNode::While(..) => self.control(Behave::While, index),
+            BehaviorNode::Idle { duration } => Tree::new(Behave::spawn_named(
+                label,
+                (Idle::default().timer_range(duration.0..duration.1), running),
+            )),
+            BehaviorNode::Wander { duration } => Tree::new(Behave::spawn_named(
+                label,
+                (
+                    Wander::builder().timer_range(duration.0..duration.1),
+                    running,
+                ),
+            )),
+            BehaviorNode::Retreat => Tree::new(Behave::spawn_named(label, (Retreat, running))),
+            BehaviorNode::Follow => Tree::new(Behave::spawn_named(label, (Follow, running))),
+            BehaviorNode::Chase => Tree::new(Behave::spawn_named(label, (Chase, running))),
+            BehaviorNode::KeepDistanceAndFire => {
+                Tree::new(Behave::spawn_named(label, (KeepDistanceAndFire, running)))
+            }
+            BehaviorNode::RaiseShield { duration } => Tree::new(Behave::spawn_named(
+                label,
+                (RaiseShield::new(*duration), running),
+            )),
+            BehaviorNode::Search { duration } => Tree::new(Behave::spawn_named(
+                label,
+                (Search::new(*duration), running),
+            )),
+            BehaviorNode::Flutter { duration } => Tree::new(Behave::spawn_named(
+                label,
+                (Flutter::timer_range(duration.0..duration.1), running),
+            )),
+            BehaviorNode::Dive => Tree::new(Behave::spawn_named(label, (Dive::default(), running))),
+            BehaviorNode::AttemptMelee => Tree::new(Behave::trigger(AttemptMelee)),
+            BehaviorNode::DashAway => Tree::new(Behave::trigger(DashAway)),
+        }
+    }
+
+    fn control(&self, node: Behave, index: &mut usize) -> Tree<Behave> {
+        let mut tree = Tree::new(node);
+        for child in self.children() {
+            tree.root_mut().append_subtree(child.tree(index));
+        }
+        tree
+    }
+
+    fn outline(&self, depth: usize, lines: &mut Vec<(usize, &'static str)>) {
+        lines.push((depth, self.label()));
+        for child in self.children() {
+            child.outline(depth + 1, lines);
+        }
+    }
+
+    fn children(&self) -> Vec<&BehaviorNode> {
+        match self {
+            BehaviorNode::Forever(child) => vec![child.as_ref()],
+            BehaviorNode::Sequence(children) | BehaviorNode::Fallback(children) => {
+                children.iter().collect()
+            }
+            BehaviorNode::While(condition, body) => vec![condition.as_ref(), body.as_ref()],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Catches trees that would deserialize fine but panic or do nothing once running
+    fn validate(&self) -> Result<(), BehaviorTreeError> {
+        match self {
+            BehaviorNode::Forever(child) => child.validate(),
+            BehaviorNode::Sequence(children) | BehaviorNode::Fallback(children) => {
+                if children.is_empty() {
+                    return Err(BehaviorTreeError::Invalid(format!(
+                        "{} needs at least one child",
+                        self.name()
+                    )));
+                }
+                children.iter().try_for_each(BehaviorNode::validate)
+            }
+            BehaviorNode::While(condition, body) => {
+                condition.validate()?;
+                body.validate()
+            }
+            BehaviorNode::Idle { duration }
+            | BehaviorNode::Wander { duration }
+            | BehaviorNode::Flutter { duration } => {
+                if duration.0 < 0.0 || duration.0 >= duration.1 {
+                    return Err(BehaviorTreeError::Invalid(format!(
+                        "{} duration ({}, {}) must be a non-negative, non-empty range",
+                        self.name(),
+                        duration.0,
+                        duration.1
+                    )));
+                }
+                Ok(())
+            }
+            BehaviorNode::RaiseShield { duration } | BehaviorNode::Search { duration } => {
+                if *duration <= 0.0 {
+                    return Err(BehaviorTreeError::Invalid(format!(
+                        "{} duration {} must be positive",
+                        self.name(),
+                        duration
+                    )));
+                }
+                Ok(())
+            }
+            BehaviorNode::Retreat
+            | BehaviorNode::Follow
+            | BehaviorNode::Chase
+            | BehaviorNode::KeepDistanceAndFire
+            | BehaviorNode::Dive
+            | BehaviorNode::AttemptMelee
+            | BehaviorNode::DashAway => Ok(()),
+        }
+    }
+
+    /// What the node is called in the debug overlay and the names of the entities it spawns
+    fn label(&self) -> &'static str {
+        match self {
+            BehaviorNode::KeepDistanceAndFire => "Keep distance and fire",
+            BehaviorNode::RaiseShield { .. } => "Raise shield",
+            BehaviorNode::AttemptMelee => "Attempt melee (trigger)",
+            BehaviorNode::DashAway => "Dash away (trigger)",
+            _ => self.name(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            BehaviorNode::Forever(_) => "Forever",
+            BehaviorNode::Sequence(_) => "Sequence",
+            BehaviorNode::Fallback(_) => "Fallback",
+            BehaviorNode::While(..) => "While",
+            BehaviorNode::Idle { .. } => "Idle",
+            BehaviorNode::Wander { .. } => "Wander",
+            BehaviorNode::Retreat => "Retreat",
+            BehaviorNode::Follow => "Follow",
+            BehaviorNode::Chase => "Chase",
+            BehaviorNode::KeepDistanceAndFire => "KeepDistanceAndFire",
+            BehaviorNode::RaiseShield { .. } => "RaiseShield",
+            BehaviorNode::Search { .. } => "Search",
+            BehaviorNode::Flutter { .. } => "Flutter",
+            BehaviorNode::Dive => "Dive",
+            BehaviorNode::AttemptMelee => "AttemptMelee",
+            BehaviorNode::DashAway => "DashAway",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BehaviorTreeError {
+    Io(std::io::Error),
+    /// Malformed RON, including node names that don't match any behavior
+    Parse(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl fmt::Display for BehaviorTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BehaviorTreeError::Io(error) => write!(f, "could not read behavior tree: {error}"),
+            BehaviorTreeError::Parse(error) => write!(f, "could not parse behavior tree: {error}"),
+            BehaviorTreeError::Invalid(reason) => write!(f, "invalid behavior tree: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for BehaviorTreeError {}
+
+impl From<std::io::Error> for BehaviorTreeError {
+    fn from(error: std::io::Error) -> Self {
+        BehaviorTreeError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for BehaviorTreeError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        BehaviorTreeError::Parse(error)
+    }
+}
+
+struct BehaviorTreeLoader;
+
+impl AssetLoader for BehaviorTreeLoader {
+    type Asset = BehaviorTreeAsset;
+    type Settings = ();
+    type Error = BehaviorTreeError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let root: BehaviorNode = from_bytes(&bytes)?;
+        root.validate()?;
+
+        Ok(BehaviorTreeAsset { root })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["behavior.ron"]
+    }
+}
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+use super::Companion;
+
+const PORTRAIT_SIZE: f32 = 40.0;
+const PORTRAIT_BORDER: f32 = 2.0;
+const PORTRAIT_BACKGROUND_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.8);
+const PORTRAIT_OUTLINE_COLOR: Color = Color::srgba(0.8, 0.8, 0.8, 0.5);
+const HEALTH_BAR_WIDTH: f32 = 80.0;
+const HEALTH_BAR_HEIGHT: f32 = 8.0;
+const HEALTH_BAR_BACKGROUND_COLOR: Color = Color::srgb(0.21, 0.21, 0.21);
+const HEALTH_BAR_COLOR: Color = Color::srgb(0.0, 0.7, 0.2);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(AppState::SpawnPlayer), spawn_companion_hud)
+        .add_systems(
+            Update,
+            update_companion_health_bars.in_set(InGameSystems::HudOverlay),
+        );
+
+    app.add_observer(add_companion_card)
+        .add_observer(remove_companion_card)
+        .add_observer(despawn_all::<RestartEvent, CompanionHud>);
+}
+
+/// Column of companion cards under the player's health and mana bars
+#[derive(Component)]
+struct CompanionHud;
+
+/// Portrait and health bar of a single companion
+#[derive(Component)]
+struct CompanionCard {
+    companion: Entity,
+}
+
+#[derive(Component)]
+struct CompanionHealthFill {
+    companion: Entity,
+}
+
+fn spawn_companion_hud(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Companion HUD"),
+        CompanionHud,
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(80.0),
+            left: px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(6.0),
+            ..default()
+        },
+    ));
+}
+
+fn add_companion_card(
+    companion: On<Add, Companion>,
+    mut commands: Commands,
+    hud: Option<Single<Entity, With<CompanionHud>>>,
+    sprite_query: Query<&Sprite>,
+    animation_config: Res<DefaultAnimationConfig>,
+) {
+    let Some(hud) = hud else {
+        return;
+    };
+
+    // Standing still and facing the camera
+    let mut portrait = ImageNode::default();
+    if let Ok(sprite) = sprite_query.get(companion.entity) {
+        portrait.image = sprite.image.clone();
+        portrait.texture_atlas = sprite.texture_atlas.as_ref().map(|atlas| TextureAtlas {
+            layout: atlas.layout.clone(),
+            index: animation_config
+                .get_indices(CharacterAnimationState::Idle, FacingDirection::Down)
+                .start(),
+        });
+    }
+
+    commands.entity(*hud).with_child((
+        CompanionCard {
+            companion: companion.entity,
+        },
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: px(6.0),
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    width: px(PORTRAIT_SIZE),
+                    height: px(PORTRAIT_SIZE),
+                    border: px(PORTRAIT_BORDER).all(),
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                BackgroundColor::from(PORTRAIT_BACKGROUND_COLOR),
+                BorderColor::from(PORTRAIT_OUTLINE_COLOR),
+                children![(
+                    portrait,
+                    Node {
+                        width: percent(100.0),
+                        height: percent(100.0),
+                        ..default()
+                    },
+                )],
+            ),
+            (
+                Node {
+                    width: px(HEALTH_BAR_WIDTH),
+                    height: px(HEALTH_BAR_HEIGHT),
+                    ..default()
+                },
+                BackgroundColor::from(HEALTH_BAR_BACKGROUND_COLOR),
+                children![(
+                    CompanionHealthFill {
+                        companion: companion.entity,
+                    },
+                    Node {
+                        width: percent(100.0),
+                        height: percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor::from(HEALTH_BAR_COLOR),
+                )],
+            ),
+        ],
+    ));
+}
+
+fn remove_companion_card(
+    companion: On<Remove, Companion>,
+    mut commands: Commands,
+    card_query: Query<(Entity, &CompanionCard)>,
+) {
+    for (card, companion_card) in &card_query {
+        if companion_card.companion == companion.entity {
+            commands.entity(card).despawn();
+        }
+    }
+}
+
+fn update_companion_health_bars(
+    mut fill_query: Query<(&mut Node, &CompanionHealthFill)>,
+    health_query: Query<&Health, (With<Companion>, Changed<Health>)>,
+) {
+    for (mut node, fill) in &mut fill_query {
+        if let Ok(health) = health_query.get(fill.companion) {
+            node.width = percent(100.0 * health.hp / health.max_hp);
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use avian2d::prelude::{RayCaster, RigidBody, SpatialQueryFilter};
+use bevy::{prelude::*, ui_widgets::observe};
+use bevy_behave::prelude::*;
+
+mod hud;
+
+use crate::{
+    character::{
+        Character, marksmanship::Marksmanship, physical_collider, threat::ThreatTable,
+        vision::VisionCapabilities,
+    },
+    prelude::*,
+};
+
+/// Most companions the player can have with them at once
+pub(super) const MAX_COMPANIONS: usize = 2;
+/// Threat companions gain per point of damage traded between the player and an enemy
+const ASSIST_THREAT: f32 = 1.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(hud::plugin);
+
+    app.add_observer(join_party)
+        .add_observer(assist_player)
+        .add_observer(despawn_all::<RestartEvent, Companion>);
+}
+
+/// Follows the player from zone to zone and fights alongside them for the rest of the run
+#[derive(Component)]
+#[require(
+    Character,
+    VisionCapabilities,
+    ThreatTable,
+    Faction = Faction::Player,
+)]
+pub struct Companion;
+
+/// Adds a companion to the player's party, ex. when hired in the hub
+#[derive(Event)]
+pub struct JoinParty {
+    pub position: Vec2,
+}
+
+fn join_party(
+    join: On<JoinParty>,
+    mut commands: Commands,
+    sprites: Res<SpriteAssets>,
+    sprite_layouts: Res<SpriteSheetLayouts>,
+    shadows: Res<Shadows>,
+    behaviors: Behaviors,
+) {
+    info!("Companion joined the party at: {}", join.position);
+
+    let behavior_source = behaviors.trees.companion.clone();
+
+    let companion = commands
+        .spawn((
+            Name::new("Companion"),
+            Companion,
+            Transform::from_translation(join.position.extend(ZLayer::OnGround.z())),
+            // A little faster than the player so they can catch up after a fight
+            SimpleMotion::new(260.0),
+            Health::new(60.0),
+            Mana::new(100.0, 10.0),
+            // Careful shots, companions hold fire rather than hit the player
+            Marksmanship {
+                accuracy: 0.8,
+                reaction_secs: 0.3,
+            },
+            // Same vision as enemies, hostile hurtboxes get added to the filter based on faction
+            RayCaster::default()
+                .with_max_distance(350.0)
+                .with_query_filter(SpatialQueryFilter::from_mask(
+                    GameCollisionLayer::HighObstacle,
+                ))
+                .with_max_hits(1),
+            Sprite::from_atlas_image(
+                sprites.game_guide_sprite_sheet.clone(),
+                TextureAtlas {
+                    layout: sprite_layouts.enemy_atlas_layout.clone(),
+                    ..default()
+                },
+            ),
+            BehaviorSource(behavior_source.clone()),
+            observe(on_companion_interaction),
+            observe(on_companion_defeated),
+            children![
+                shadow(&shadows, CHARACTER_FEET_POS_OFFSET - 4.0),
+                (
+                    InteractionZone::NPC,
+                    Transform::from_xyz(0.0, CHARACTER_FEET_POS_OFFSET, 0.0),
+                ),
+                hurtbox(Vec2::new(26.0, 42.0), Faction::Player),
+                physical_collider(),
+                BehaveTree::new(behaviors.tree(&behavior_source)),
+            ],
+        ))
+        .id();
+
+    let mainhand = commands.spawn(ice_staff(&sprites, &sprite_layouts)).id();
+    commands.trigger(Equip {
+        item: mainhand,
+        holder: companion,
+    });
+}
+
+/// Companions join in on whatever fight the player is in. Whoever the player hurts, or gets hurt
+/// by, becomes a threat to them as well.
+fn assist_player(
+    damage_dealt: On<DamageDealt>,
+    player: Option<Single<Entity, With<Player>>>,
+    mut companion_query: Query<(&mut ThreatTable, &Faction), With<Companion>>,
+    faction_query: Query<&Faction>,
+    weapon_query: Query<&ItemOf>,
+    projectile_query: Query<&FiredBy>,
+    relations: Res<FactionRelations>,
+) {
+    let Some(player) = player.map(|player| *player) else {
+        return;
+    };
+
+    let attacker = damage_dealt.damage_source.and_then(|source| {
+        weapon_query
+            .get(source)
+            .map(|item_of| item_of.0)
+            .or_else(|_| projectile_query.get(source).map(|fired_by| fired_by.0))
+            .ok()
+    });
+
+    let enemy = if damage_dealt.entity == player {
+        attacker
+    } else if attacker == Some(player) {
+        Some(damage_dealt.entity)
+    } else {
+        None
+    };
+
+    let Some(enemy) = enemy else {
+        return;
+    };
+    let Ok(enemy_faction) = faction_query.get(enemy) else {
+        return;
+    };
+
+    for (mut threat_table, faction) in &mut companion_query {
+        if relations.is_hostile(*faction, *enemy_faction) {
+            threat_table.add(enemy, damage_dealt.damage * ASSIST_THREAT);
+        }
+    }
+}
+
+/// Talking to a hurt companion hands them the first healing potion in the player's inventory
+fn on_companion_interaction(
+    interaction: On<PlayerInteraction>,
+    mut commands: Commands,
+    companion_query: Query<&Health, With<Companion>>,
+    player: Single<Option<&Items>, With<Player>>,
+    consumable_query: Query<&Consumable>,
+) {
+    let Ok(health) = companion_query.get(interaction.entity) else {
+        return;
+    };
+
+    if health.hp >= health.max_hp {
+        return;
+    }
+
+    let potion = player.into_inner().and_then(|items| {
+        items.iter().find(|item| {
+            consumable_query
+                .get(*item)
+                .is_ok_and(|consumable| matches!(consumable.effect, ConsumableType::Heal(_)))
+        })
+    });
+
+    if let Some(potion) = potion {
+        commands.trigger(Consume {
+            entity: interaction.entity,
+            item_entity: potion,
+        });
+    } else {
+        info!("No healing potion to give to companion");
+    }
+}
+
+fn on_companion_defeated(defeated: On<Defeated>, mut commands: Commands) {
+    info!("Companion {} was defeated", defeated.entity);
+
+    commands
+        .entity(defeated.entity)
+        .insert(Lifespan::new(2.0))
+        .remove::<(Companion, Health, RigidBody)>()
+        .despawn_related::<Children>();
+}
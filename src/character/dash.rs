@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Extra dash distance, as a percentage, per point of agility
+const AGILITY_DASH_SCALING: f32 = 0.05;
+
+/// How much longer than the dash itself the character stays invulnerable
+const DASH_IFRAME_GRACE_SECS: f32 = 0.1;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        tick_dash_cooldowns.in_set(InGameSystems::Simulation),
+    )
+    .add_observer(on_attempt_dash);
+}
+
+/// Gives a character the ability to quickly dash in a direction
+#[derive(Component, Clone)]
+pub struct Dash {
+    /// Distance covered before any stat scaling
+    pub distance: f32,
+    /// How long it takes to cover `distance`, in seconds
+    pub duration: f32,
+    pub cooldown: Timer,
+}
+
+impl Dash {
+    pub fn new(distance: f32, cooldown_secs: f32) -> Self {
+        let mut cooldown = Timer::from_seconds(cooldown_secs, TimerMode::Once);
+        // Dash is ready to use as soon as it is spawned
+        cooldown.set_elapsed(cooldown.duration());
+
+        Self {
+            distance,
+            duration: 0.15,
+            cooldown,
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.cooldown.is_finished()
+    }
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        Self::new(96.0, 1.0)
+    }
+}
+
+/// Present while a character is mid-dash, overriding their normal motion
+#[derive(Component)]
+pub struct Dashing {
+    velocity: Vec2,
+    timer: Timer,
+}
+
+impl Dashing {
+    /// Moves the character `distance` along `direction` over `duration_secs`, without any of the
+    /// cooldown or invulnerability of a regular dash (ex. a charged melee lunge)
+    pub fn new(direction: Vec2, distance: f32, duration_secs: f32) -> Self {
+        Self {
+            velocity: direction.normalize_or_zero() * (distance / duration_secs),
+            timer: Timer::from_seconds(duration_secs, TimerMode::Once),
+        }
+    }
+}
+
+#[derive(EntityEvent)]
+pub struct AttemptDash {
+    pub entity: Entity,
+    pub direction: Vec2,
+}
+
+/// Triggered when a character successfully starts dashing
+#[derive(EntityEvent)]
+pub struct Dashed {
+    pub entity: Entity,
+}
+
+fn on_attempt_dash(
+    attempt_dash: On<AttemptDash>,
+    mut commands: Commands,
+    mut dasher_query: Query<
+        (
+            &mut Dash,
+            &SimpleMotion,
+            Option<&PlayerStats>,
+            Option<&mut IFrames>,
+        ),
+        Without<Dashing>,
+    >,
+) {
+    let Ok((mut dash, motion, stats, iframes)) = dasher_query.get_mut(attempt_dash.entity) else {
+        return;
+    };
+
+    let Ok(direction) = Dir2::new(attempt_dash.direction) else {
+        return;
+    };
+
+    if !dash.is_ready() || motion.is_stunned() {
+        return;
+    }
+
+    let distance =
+        dash.distance * (1.0 + stats.map_or(0.0, |s| s.agility as f32 * AGILITY_DASH_SCALING));
+
+    if let Some(mut iframes) = iframes {
+        iframes.grant(Duration::from_secs_f32(
+            dash.duration + DASH_IFRAME_GRACE_SECS,
+        ));
+    }
+
+    dash.cooldown.reset();
+    commands.entity(attempt_dash.entity).insert(Dashing {
+        velocity: direction * (distance / dash.duration),
+        timer: Timer::from_seconds(dash.duration, TimerMode::Once),
+    });
+    commands.trigger(Dashed {
+        entity: attempt_dash.entity,
+    });
+}
+
+fn tick_dash_cooldowns(mut dash_query: Query<&mut Dash>, time: Res<Time>) {
+    for mut dash in &mut dash_query {
+        dash.cooldown.tick(time.delta());
+    }
+}
+
+/// Runs after `motion_to_velocity` so the dash wins over regular movement
+pub(super) fn dash_to_velocity(
+    mut commands: Commands,
+    mut dashing_query: Query<(Entity, &mut Dashing, &mut LinearVelocity)>,
+    time: Res<Time>,
+) {
+    for (entity, mut dashing, mut velocity) in &mut dashing_query {
+        if dashing.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<Dashing>();
+        } else {
+            velocity.0 = dashing.velocity;
+        }
+    }
+}
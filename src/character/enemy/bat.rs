@@ -0,0 +1,149 @@
+use std::ops::RangeInclusive;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use bevy_behave::prelude::*;
+
+use crate::{
+    character::{
+        Purse,
+        hearing::Hearing,
+        reflexes::{Evasion, Reflexes},
+    },
+    prelude::*,
+};
+
+/// How many bats come out of a single enemy spawn
+pub(super) const SWARM_SIZE: RangeInclusive<usize> = 3..=5;
+/// Bats in a swarm spawn scattered up to this far from the spawn point
+pub(super) const SWARM_SPREAD: f32 = 40.0;
+/// Bats fly high, their shadow is drawn well below them
+const FLYING_HEIGHT: f32 = 28.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        deal_contact_damage.in_set(InGameSystems::Simulation),
+    );
+}
+
+pub(super) fn bat(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
+    (
+        SimpleMotion::new(220.0),
+        Health::new(10.0),
+        // Light enough that any solid hit knocks them out of the air
+        Poise::new(4.0, 4.0),
+        // Twitchy, they flit out of the way of most things thrown at them
+        Reflexes::new(0.7, Evasion::Strafe),
+        // Keen ears, a swarm will come looking from much further away than anything else
+        Hearing { sensitivity: 1.5 },
+        Experience { base_exp: 4.0 },
+        Purse { amount: 10 },
+        AnimationSheet::Bat,
+        YSort::from_offset(-FLYING_HEIGHT),
+        Sprite::from_atlas_image(
+            sprites.bat_enemy_sprite_sheet.clone(),
+            TextureAtlas {
+                layout: sprite_layouts.bat_enemy_layout.clone(),
+                ..default()
+            },
+        ),
+    )
+}
+
+pub(super) fn bat_children(behavior: Tree<Behave>, shadows: &Shadows) -> impl Bundle {
+    children![
+        shadow(shadows, -FLYING_HEIGHT),
+        flying_collider(),
+        hurtbox(Vec2::new(20.0, 16.0), Faction::Enemy),
+        contact_hit_box(),
+        BehaveTree::new(behavior),
+    ]
+}
+
+/// Flyers are stopped by walls but pass over low obstacles like chests, and only bump into other
+/// flyers
+fn flying_collider() -> impl Bundle {
+    (
+        Collider::circle(8.0),
+        CollisionLayers::new(
+            [GameCollisionLayer::InAir],
+            [GameCollisionLayer::InAir, GameCollisionLayer::HighObstacle],
+        ),
+    )
+}
+
+/// Hurts hostile characters it touches while its owner is mid-dash, each of them once per dash
+#[derive(Component)]
+#[require(Sensor, CollidingEntities)]
+struct ContactHitBox {
+    damage: Damage,
+    hits: Vec<Entity>,
+}
+
+fn contact_hit_box() -> impl Bundle {
+    (
+        ContactHitBox {
+            damage: Damage::Range((3.0, 5.0)),
+            hits: Vec::new(),
+        },
+        Collider::circle(12.0),
+        // Hostility is checked on contact so faction changes apply to the next dive
+        CollisionLayers::new(
+            GameCollisionLayer::HitBox,
+            [
+                GameCollisionLayer::PlayerHurtBox,
+                GameCollisionLayer::EnemyHurtBox,
+                GameCollisionLayer::VillagerHurtBox,
+                GameCollisionLayer::WildlifeHurtBox,
+            ],
+        ),
+    )
+}
+
+fn deal_contact_damage(
+    mut commands: Commands,
+    mut hit_box_query: Query<(&ChildOf, &CollidingEntities, &mut ContactHitBox)>,
+    owner_query: Query<(&Faction, &Transform, Has<Dashing>)>,
+    hurt_box_query: Query<(&ChildOf, &GlobalTransform), With<HurtBox>>,
+    faction_query: Query<&Faction>,
+    relations: Res<FactionRelations>,
+) {
+    for (child_of, colliding_entities, mut contact) in &mut hit_box_query {
+        let owner = child_of.parent();
+        let Ok((owner_faction, owner_transform, dashing)) = owner_query.get(owner) else {
+            continue;
+        };
+
+        if !dashing {
+            contact.hits.clear();
+            continue;
+        }
+
+        for &hurt_box in colliding_entities.iter() {
+            let Ok((hurt_child_of, hurt_box_transform)) = hurt_box_query.get(hurt_box) else {
+                continue;
+            };
+
+            let hostile = faction_query
+                .get(hurt_child_of.parent())
+                .is_ok_and(|faction| relations.is_hostile(*owner_faction, *faction));
+
+            if !hostile || contact.hits.contains(&hurt_box) {
+                continue;
+            }
+
+            contact.hits.push(hurt_box);
+            commands.trigger(AttemptDamage {
+                entity: hurt_box,
+                damage: contact.damage,
+                damage_source: Some(owner),
+                direction: Some(
+                    (hurt_box_transform.translation().xy() - owner_transform.translation.xy())
+                        .normalize_or_zero(),
+                ),
+                ..default()
+            });
+        }
+    }
+}
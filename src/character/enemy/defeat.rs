@@ -16,7 +16,14 @@ pub(super) fn on_enemy_defeated(
     defeated: On<Defeated>,
     mut commands: Commands,
     mut defeated_enemy_query: Query<
-        (&Experience, &Transform, Option<&Items>, Option<&Purse>),
+        (
+            &Experience,
+            &Transform,
+            &Faction,
+            Option<&Items>,
+            Option<&Purse>,
+            Has<GroundEffects>,
+        ),
         With<Enemy>,
     >,
     player_query: Single<(&PlayerStats, &mut Player)>,
@@ -24,7 +31,7 @@ pub(super) fn on_enemy_defeated(
 ) {
     let mut rng = rng();
 
-    if let Ok((experience_to_gain, transform, items, purse)) =
+    if let Ok((experience_to_gain, transform, faction, items, purse, has_ground_effects)) =
         defeated_enemy_query.get_mut(defeated.entity)
     {
         let (player_stats, mut player) = player_query.into_inner();
@@ -55,6 +62,15 @@ pub(super) fn on_enemy_defeated(
             }
         }
 
+        // Some enemies leave something nasty behind when they die
+        if has_ground_effects {
+            commands.trigger(SpawnGroundEffects {
+                entity: defeated.entity,
+                position: transform.translation.truncate(),
+                faction: *faction,
+            });
+        }
+
         commands
             .entity(defeated.entity)
             .insert(Lifespan::new(2.0))
@@ -0,0 +1,350 @@
+use std::{collections::HashSet, f32::consts::TAU};
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::{Rng, rng};
+
+use crate::{character::Purse, prelude::*};
+
+use super::{EnemySpawnData, EnemyType, Experience, SpawnEnemies};
+
+const ELITE_HEALTH_MULTIPLIER: f32 = 1.75;
+const ELITE_EXPERIENCE_MULTIPLIER: f32 = 3.0;
+const ELITE_PURSE_MULTIPLIER: u32 = 3;
+/// Chance of rolling each affix after the first, up to `MAX_AFFIXES`
+const EXTRA_AFFIX_CHANCE: f64 = 0.35;
+const MAX_AFFIXES: usize = 3;
+const FAST_SPEED_MULTIPLIER: f32 = 1.4;
+/// Burning elites drop a new fire pool once they've moved this far from the last one
+const BURNING_AURA_SPACING: f32 = 32.0;
+/// or once the last pool has burnt out, if they haven't moved
+const BURNING_AURA_REFRESH_SECS: f32 = 4.0;
+/// Fraction of the damage they deal vampiric elites heal for
+const VAMPIRIC_LIFESTEAL: f32 = 0.5;
+const FROZEN_TOUCH_SECS: f32 = 1.0;
+/// Projectiles are turned around this far out from a shielded elite, before reaching its hurtbox
+const REFLECTIVE_SHELL_RADIUS: f32 = 30.0;
+/// How many minions a splitter breaks into, bats bring a whole swarm each
+const SPLIT_COUNT: usize = 2;
+const SPLIT_SPREAD: f32 = 24.0;
+const SPLIT_MINION_HEALTH_MULTIPLIER: f32 = 0.5;
+const SPLIT_MINION_SCALE: f32 = 0.7;
+/// Height above the enemy's center the name plate floats at, just over its health bar
+const NAME_PLATE_OFFSET: f32 = 52.0;
+const NAME_PLATE_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, trail_burning_aura.in_set(InGameSystems::Simulation))
+        .add_observer(on_elite_added)
+        .add_observer(on_split_minion_added)
+        .add_observer(on_elite_hit)
+        .add_observer(split_on_defeat);
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Affix {
+    Fast,
+    /// Leaves a trail of fire behind it
+    BurningAura,
+    /// Heals for part of the damage it deals
+    Vampiric,
+    /// Reflects projectiles back at whoever fired them
+    Shielded,
+    /// Breaks into smaller copies of itself when defeated
+    Splitter,
+    /// Freezes whoever it hits
+    FrozenTouch,
+}
+
+impl Affix {
+    pub const ALL: [Affix; 6] = [
+        Affix::Fast,
+        Affix::BurningAura,
+        Affix::Vampiric,
+        Affix::Shielded,
+        Affix::Splitter,
+        Affix::FrozenTouch,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Affix::Fast => "Fast",
+            Affix::BurningAura => "Burning",
+            Affix::Vampiric => "Vampiric",
+            Affix::Shielded => "Shielded",
+            Affix::Splitter => "Splitter",
+            Affix::FrozenTouch => "Frozen Touch",
+        }
+    }
+
+    /// Elites are tinted by their first affix
+    fn tint(self) -> Color {
+        match self {
+            Affix::Fast => Color::srgb(1.0, 1.0, 0.6),
+            Affix::BurningAura => Color::srgb(1.0, 0.6, 0.4),
+            Affix::Vampiric => Color::srgb(0.8, 0.4, 0.5),
+            Affix::Shielded => Color::srgb(0.7, 0.8, 1.0),
+            Affix::Splitter => Color::srgb(0.6, 1.0, 0.6),
+            Affix::FrozenTouch => Color::srgb(0.6, 0.95, 1.0),
+        }
+    }
+}
+
+/// Tougher, better rewarded version of a regular enemy, rolled at spawn time with one or more
+/// affixes
+#[derive(Component, Clone, Debug)]
+pub struct Elite {
+    pub affixes: Vec<Affix>,
+}
+
+impl Elite {
+    /// One affix, with a chance at each extra one up to `MAX_AFFIXES`, never the same one twice
+    pub fn roll() -> Self {
+        let mut rng = rng();
+        let mut remaining = Affix::ALL.to_vec();
+        let mut affixes = Vec::new();
+
+        while affixes.len() < MAX_AFFIXES
+            && (affixes.is_empty() || rng.random_bool(EXTRA_AFFIX_CHANCE))
+        {
+            affixes.push(remaining.swap_remove(rng.random_range(0..remaining.len())));
+        }
+
+        Self { affixes }
+    }
+
+    pub fn has(&self, affix: Affix) -> bool {
+        self.affixes.contains(&affix)
+    }
+}
+
+/// Smaller, weaker copy of a regular enemy left behind by a splitter elite
+#[derive(Component)]
+pub struct SplitMinion;
+
+/// Drops fire pools under the elite it's a child of
+#[derive(Component)]
+struct BurningAura {
+    last_pool: Option<Vec2>,
+    refresh: Timer,
+}
+
+fn on_elite_added(
+    elite_added: On<Add, Elite>,
+    mut commands: Commands,
+    mut elite_query: Query<(
+        &Elite,
+        &mut Health,
+        &mut SimpleMotion,
+        &mut Experience,
+        &mut Purse,
+        &mut Sprite,
+    )>,
+    sprites: Res<SpriteAssets>,
+    sprite_layouts: Res<SpriteSheetLayouts>,
+) -> Result {
+    let (elite, mut health, mut motion, mut experience, mut purse, mut sprite) =
+        elite_query.get_mut(elite_added.entity)?;
+
+    info!("Elite rolled with affixes: {:?}", elite.affixes);
+
+    health.max_hp *= ELITE_HEALTH_MULTIPLIER;
+    health.hp = health.max_hp;
+    experience.base_exp *= ELITE_EXPERIENCE_MULTIPLIER;
+    purse.amount *= ELITE_PURSE_MULTIPLIER;
+
+    let tint = elite
+        .affixes
+        .first()
+        .map_or(Color::WHITE, |affix| affix.tint());
+    sprite.color = tint;
+
+    let mut entity = commands.entity(elite_added.entity);
+    entity
+        .insert(SpriteTint(tint))
+        .with_child(name_plate(elite));
+
+    for affix in &elite.affixes {
+        match affix {
+            Affix::Fast => motion.max_speed *= FAST_SPEED_MULTIPLIER,
+            Affix::BurningAura => {
+                entity.with_child((
+                    Name::new("Burning Aura"),
+                    BurningAura {
+                        last_pool: None,
+                        refresh: Timer::from_seconds(BURNING_AURA_REFRESH_SECS, TimerMode::Once),
+                    },
+                    related!(GroundEffects[fire_pool(&sprites, &sprite_layouts)]),
+                ));
+            }
+            Affix::Shielded => {
+                entity.with_child(reflective_shell());
+            }
+            // Take effect when the elite lands a hit or is defeated
+            Affix::Vampiric | Affix::Splitter | Affix::FrozenTouch => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn name_plate(elite: &Elite) -> impl Bundle {
+    let affixes: Vec<&str> = elite.affixes.iter().map(|affix| affix.name()).collect();
+
+    (
+        Name::new("Elite Name Plate"),
+        Text2d::new(affixes.join(" ")),
+        TextFont {
+            font_size: 10.0,
+            ..default()
+        },
+        TextColor(NAME_PLATE_COLOR),
+        Transform::from_xyz(0.0, NAME_PLATE_OFFSET, ZLayer::AboveSprite.z()),
+    )
+}
+
+fn on_split_minion_added(
+    minion_added: On<Add, SplitMinion>,
+    mut minion_query: Query<(&mut Health, &mut Transform)>,
+) -> Result {
+    let (mut health, mut transform) = minion_query.get_mut(minion_added.entity)?;
+
+    health.max_hp *= SPLIT_MINION_HEALTH_MULTIPLIER;
+    health.hp = health.max_hp;
+    transform.scale *= SPLIT_MINION_SCALE;
+
+    Ok(())
+}
+
+/// Always raised shield surrounding the elite
+fn reflective_shell() -> impl Bundle {
+    (
+        Name::new("Reflective Shell"),
+        ProjectileReflection,
+        ActiveShield {
+            projectiles_reflected: HashSet::default(),
+            parry_window: Timer::default(),
+        },
+        Collider::circle(REFLECTIVE_SHELL_RADIUS),
+        CollisionLayers::new(
+            GameCollisionLayer::ProjectileReflector,
+            GameCollisionLayer::InAir,
+        ),
+    )
+}
+
+fn trail_burning_aura(
+    mut commands: Commands,
+    mut aura_query: Query<(Entity, &mut BurningAura, &ChildOf)>,
+    owner_query: Query<(&Transform, &Faction)>,
+    time: Res<Time>,
+) {
+    for (aura, mut burning_aura, child_of) in &mut aura_query {
+        let Ok((transform, faction)) = owner_query.get(child_of.parent()) else {
+            continue;
+        };
+
+        let position = transform.translation.xy() + Vec2::new(0.0, CHARACTER_FEET_POS_OFFSET);
+        let moved = burning_aura
+            .last_pool
+            .is_none_or(|last_pool| last_pool.distance(position) > BURNING_AURA_SPACING);
+
+        if !burning_aura.refresh.tick(time.delta()).is_finished() && !moved {
+            continue;
+        }
+
+        burning_aura.last_pool = Some(position);
+        burning_aura.refresh.reset();
+
+        commands.trigger(SpawnGroundEffects {
+            entity: aura,
+            position,
+            faction: *faction,
+        });
+    }
+}
+
+/// Vampiric and frozen touch elites, whether they hit with a weapon, projectile or their body
+fn on_elite_hit(
+    damage_dealt: On<DamageDealt>,
+    mut commands: Commands,
+    elite_query: Query<&Elite>,
+    health_query: Query<&Health>,
+    weapon_query: Query<&ItemOf>,
+    projectile_query: Query<&FiredBy>,
+) {
+    let Some(attacker) = damage_dealt.damage_source.map(|source| {
+        weapon_query
+            .get(source)
+            .map(|item_of| item_of.0)
+            .or_else(|_| projectile_query.get(source).map(|fired_by| fired_by.0))
+            .unwrap_or(source)
+    }) else {
+        return;
+    };
+
+    let Ok(elite) = elite_query.get(attacker) else {
+        return;
+    };
+
+    if attacker == damage_dealt.entity {
+        return;
+    }
+
+    if elite.has(Affix::Vampiric) {
+        commands.trigger(AttemptHeal {
+            entity: attacker,
+            amount: damage_dealt.damage * VAMPIRIC_LIFESTEAL,
+            healer: Some(attacker),
+        });
+    }
+
+    if elite.has(Affix::FrozenTouch)
+        && health_query
+            .get(damage_dealt.entity)
+            .is_ok_and(|health| health.hp > 0.0)
+    {
+        commands.spawn((
+            Name::new("Frozen"),
+            Frozen,
+            Lifespan::new(FROZEN_TOUCH_SECS),
+            StatusOf(damage_dealt.entity),
+        ));
+    }
+}
+
+fn split_on_defeat(
+    defeated: On<Defeated>,
+    mut commands: Commands,
+    elite_query: Query<(&Elite, &EnemyType, &Transform)>,
+) {
+    let Ok((elite, enemy_type, transform)) = elite_query.get(defeated.entity) else {
+        return;
+    };
+
+    if !elite.has(Affix::Splitter) {
+        return;
+    }
+
+    let count = if *enemy_type == EnemyType::Bat {
+        1
+    } else {
+        SPLIT_COUNT
+    };
+
+    let mut rng = rng();
+    let position = transform.translation.xy();
+
+    // Minions are never elites themselves, or splitting could go on forever
+    commands.trigger(SpawnEnemies(
+        (0..count)
+            .map(|_| EnemySpawnData {
+                position: position + Vec2::from_angle(rng.random_range(0.0..TAU)) * SPLIT_SPREAD,
+                enemy_type: enemy_type.clone(),
+                boss: false,
+                elite_chance: 0.0,
+                minion: true,
+            })
+            .collect(),
+    ));
+}
@@ -1,20 +1,33 @@
+use std::f32::consts::TAU;
+
 use avian2d::prelude::{RayCaster, SpatialQueryFilter};
 use bevy::{prelude::*, ui_widgets::observe};
 use bevy_behave::prelude::*;
+use rand::{Rng, rng};
 
+mod bat;
 mod defeat;
+mod elite;
+
+pub use elite::{Affix, Elite, SplitMinion};
 
 use crate::{
     character::{
         Character, Purse,
-        behavior::{Anchor, AttemptMelee, Chase, Idle, KeepDistanceAndFire, Retreat, Wander},
+        behavior::Anchor,
+        hearing::Hearing,
+        marksmanship::Marksmanship,
         physical_collider,
-        vision::{VisionCapabilities, Watching},
+        reflexes::{Evasion, Reflexes},
+        threat::ThreatTable,
+        vision::VisionCapabilities,
     },
     prelude::*,
 };
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((bat::plugin, elite::plugin));
+
     app.add_observer(spawn_enemies);
 
     app.add_observer(despawn_all::<CleanupZone, Enemy>);
@@ -27,6 +40,11 @@ pub struct SpawnEnemies(pub Vec<EnemySpawnData>);
 pub struct EnemySpawnData {
     pub position: Vec2,
     pub enemy_type: EnemyType,
+    pub boss: bool,
+    /// Chance of a regular enemy being rolled as an `Elite`, bosses never are
+    pub elite_chance: f32,
+    /// Spawned by a splitter elite breaking apart, see `SplitMinion`
+    pub minion: bool,
 }
 
 #[derive(Component)]
@@ -34,7 +52,10 @@ pub struct EnemySpawnData {
     Character,
     Experience,
     VisionCapabilities,
+    ThreatTable,
+    Hearing,
     Purse { amount: 50 },
+    Faction = Faction::Enemy,
 )]
 pub struct Enemy;
 
@@ -43,6 +64,45 @@ pub enum EnemyType {
     Warrior,
     IceMage,
     FireMage,
+    /// Flies over low obstacles and comes in swarms
+    Bat,
+}
+
+impl EnemyType {
+    /// The behavior tree asset this kind of enemy runs
+    pub fn behavior<'a>(&self, trees: &'a BehaviorTrees) -> &'a Handle<BehaviorTreeAsset> {
+        match self {
+            EnemyType::Warrior => &trees.melee_enemy,
+            EnemyType::IceMage | EnemyType::FireMage => &trees.ranged_enemy,
+            EnemyType::Bat => &trees.bat,
+        }
+    }
+}
+
+/// Bosses are much tougher versions of regular enemies, they get a named health bar across the
+/// top of the screen instead of a world-space one
+#[derive(Component, Clone)]
+pub struct Boss {
+    pub name: String,
+}
+
+fn boss(enemy_type: &EnemyType) -> impl Bundle {
+    let (name, max_hp) = match enemy_type {
+        EnemyType::Warrior => ("Grask the Unbroken", 200.0),
+        EnemyType::IceMage => ("Ysolde, Frost Witch", 120.0),
+        EnemyType::FireMage => ("Cinder Lord Vhar", 120.0),
+        EnemyType::Bat => ("Vesper, Mother of the Swarm", 80.0),
+    };
+
+    (
+        Boss {
+            name: name.to_string(),
+        },
+        Health::new(max_hp),
+        Poise::new(60.0, 15.0),
+        Experience { base_exp: 100.0 },
+        Purse { amount: 500 },
+    )
 }
 
 //Experience granted by the enemy when player defeats it
@@ -63,17 +123,34 @@ fn spawn_enemies(
     sprites: Res<SpriteAssets>,
     sprite_layouts: Res<SpriteSheetLayouts>,
     shadows: Res<Shadows>,
-    player: Single<Entity, With<Player>>,
+    behaviors: Behaviors,
 ) {
+    let mut rng = rng();
+
     for spawn_data in spawn_enemies.0.clone() {
-        spawn_enemy(
-            &mut commands,
-            spawn_data,
-            &sprites,
-            &sprite_layouts,
-            &shadows,
-            player.entity(),
-        );
+        // Every regular bat spawn brings the rest of its swarm, bosses fly alone
+        let swarm_size = if spawn_data.enemy_type == EnemyType::Bat && !spawn_data.boss {
+            rng.random_range(bat::SWARM_SIZE)
+        } else {
+            1
+        };
+
+        for i in 0..swarm_size {
+            let mut spawn_data = spawn_data.clone();
+            if i > 0 {
+                spawn_data.position += Vec2::from_angle(rng.random_range(0.0..TAU))
+                    * rng.random_range(0.0..bat::SWARM_SPREAD);
+            }
+
+            spawn_enemy(
+                &mut commands,
+                spawn_data,
+                &sprites,
+                &sprite_layouts,
+                &shadows,
+                &behaviors,
+            );
+        }
     }
 }
 
@@ -83,77 +160,106 @@ fn spawn_enemy(
     sprites: &SpriteAssets,
     sprite_layouts: &SpriteSheetLayouts,
     shadows: &Shadows,
-    player: Entity,
+    behaviors: &Behaviors,
 ) {
     info!("Spawning enemy at: {}", spawn_data.position);
 
-    let chase_behavior = behave! {
-        Behave::While => {
-            Behave::spawn_named("Chase", Chase),
-            Behave::trigger(AttemptMelee)
-        }
-    };
+    let behavior_source = spawn_data.enemy_type.behavior(&behaviors.trees);
+    let behavior = behaviors.tree(behavior_source);
 
-    let melee_enemy_behavior = behave! {
-        Behave::Forever => {
-            Behave::Fallback => {
-                Behave::Sequence => {
-                    Behave::spawn_named("Wander", Wander::builder().timer_range(1.0..2.0)),
-                    Behave::spawn_named("Idle", Idle::default().timer_range(3.0..5.0)),
-                },
-                Behave::spawn_named("Retreat", Retreat),
-                @chase_behavior
-            }
-        }
-    };
+    let enemy = match spawn_data.enemy_type {
+        EnemyType::Warrior => {
+            let warrior = spawn_enemy_with_equipment(
+                commands,
+                (
+                    warrior(sprites, sprite_layouts),
+                    base_enemy(spawn_data.position),
+                    enemy_children(behavior, shadows),
+                ),
+                sword(sprites),
+            );
 
-    let ranged_enemy_behavior = behave! {
-        Behave::Forever => {
-            Behave::Fallback => {
-                Behave::Sequence => {
-                    Behave::spawn_named("Wander", Wander::builder().timer_range(1.0..2.0)),
-                    Behave::spawn_named("Idle", Idle::default().timer_range(3.0..5.0)),
-                },
-                Behave::spawn_named("Retreat", Retreat),
-                Behave::spawn_named("Keep distance and fire", KeepDistanceAndFire)
-            }
+            let shield = commands.spawn(knight_shield(sprites, sprite_layouts)).id();
+            commands.trigger(Equip {
+                item: shield,
+                holder: warrior,
+            });
+
+            warrior
         }
-    };
 
-    match spawn_data.enemy_type {
-        EnemyType::Warrior => spawn_enemy_with_equipment(
+        // Boss mages wield staffs with projectile modifiers
+        EnemyType::IceMage if spawn_data.boss => spawn_enemy_with_equipment(
             commands,
             (
-                warrior(sprites, sprite_layouts),
-                base_enemy(spawn_data.position, player),
-                enemy_children(melee_enemy_behavior, shadows),
+                ice_mage(sprites, sprite_layouts),
+                base_enemy(spawn_data.position),
+                enemy_children(behavior, shadows),
             ),
-            sword(sprites),
+            shatter_staff(sprites, sprite_layouts),
         ),
 
         EnemyType::IceMage => spawn_enemy_with_equipment(
             commands,
             (
                 ice_mage(sprites, sprite_layouts),
-                base_enemy(spawn_data.position, player),
-                enemy_children(ranged_enemy_behavior, shadows),
+                base_enemy(spawn_data.position),
+                enemy_children(behavior, shadows),
             ),
             ice_staff(sprites, sprite_layouts),
         ),
 
+        EnemyType::FireMage if spawn_data.boss => spawn_enemy_with_equipment(
+            commands,
+            (
+                fire_mage(sprites, sprite_layouts),
+                base_enemy(spawn_data.position),
+                enemy_children(behavior, shadows),
+            ),
+            cinder_staff(sprites, sprite_layouts),
+        ),
+
         EnemyType::FireMage => spawn_enemy_with_equipment(
             commands,
             (
                 fire_mage(sprites, sprite_layouts),
-                base_enemy(spawn_data.position, player),
-                enemy_children(ranged_enemy_behavior, shadows),
+                base_enemy(spawn_data.position),
+                enemy_children(behavior, shadows),
             ),
             fire_staff(sprites, sprite_layouts),
         ),
+
+        EnemyType::Bat => commands
+            .spawn((
+                bat::bat(sprites, sprite_layouts),
+                base_enemy(spawn_data.position),
+                bat::bat_children(behavior, shadows),
+            ))
+            .id(),
+    };
+
+    commands.entity(enemy).insert((
+        spawn_data.enemy_type.clone(),
+        BehaviorSource(behavior_source.clone()),
+    ));
+
+    if spawn_data.boss {
+        commands
+            .entity(enemy)
+            .insert(boss(&spawn_data.enemy_type))
+            .with_related::<ItemOf>(revive_token(sprites));
+    } else if spawn_data.minion {
+        commands.entity(enemy).insert(SplitMinion);
+    } else if rng().random_bool(f64::from(spawn_data.elite_chance.clamp(0.0, 1.0))) {
+        commands.entity(enemy).insert(Elite::roll());
     }
 }
 
-fn spawn_enemy_with_equipment(commands: &mut Commands, enemy: impl Bundle, mainhand: impl Bundle) {
+fn spawn_enemy_with_equipment(
+    commands: &mut Commands,
+    enemy: impl Bundle,
+    mainhand: impl Bundle,
+) -> Entity {
     let enemy = commands.spawn(enemy).id();
 
     let mainhand = commands.spawn(mainhand).id();
@@ -162,23 +268,23 @@ fn spawn_enemy_with_equipment(commands: &mut Commands, enemy: impl Bundle, mainh
         item: mainhand,
         holder: enemy,
     });
+
+    enemy
 }
 
-fn base_enemy(position: Vec2, player: Entity) -> impl Bundle {
+fn base_enemy(position: Vec2) -> impl Bundle {
     (
         Enemy,
         Transform::from_translation(position.extend(ZLayer::OnGround.z())),
         Anchor::new(position, 256.0), // 8 tile radius
         Mana::new(100.0, 10.0),
-        // enemy vision distance
+        // enemy vision distance, hostile hurtboxes get added to the filter based on faction
         RayCaster::default()
             .with_max_distance(350.0)
-            .with_query_filter(SpatialQueryFilter::from_mask([
-                GameCollisionLayer::AllyHurtBox,
+            .with_query_filter(SpatialQueryFilter::from_mask(
                 GameCollisionLayer::HighObstacle,
-            ]))
+            ))
             .with_max_hits(1),
-        Watching(player),
         observe(defeat::on_enemy_defeated),
     )
 }
@@ -187,7 +293,7 @@ fn enemy_children(behavior: Tree<Behave>, shadows: &Shadows) -> impl Bundle {
     children![
         shadow(shadows, CHARACTER_FEET_POS_OFFSET - 4.0),
         physical_collider(),
-        hurtbox(Vec2::new(26.0, 42.0), GameCollisionLayer::EnemyHurtBox),
+        hurtbox(Vec2::new(26.0, 42.0), Faction::Enemy),
         BehaveTree::new(behavior.clone()),
     ]
 }
@@ -196,6 +302,12 @@ fn warrior(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl
     (
         SimpleMotion::new(200.0),
         Health::new(40.0),
+        // Warriors are heavy, they take a beating before being staggered
+        Poise::new(25.0, 8.0),
+        KnockbackResistance(0.6),
+        Armor::new(1.0, 0.1),
+        PackRole::Flanker,
+        Reflexes::new(0.5, Evasion::Block),
         Sprite::from_atlas_image(
             sprites.warrior_enemy_sprite_sheet.clone(),
             TextureAtlas {
@@ -211,6 +323,14 @@ fn ice_mage(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl
     (
         SimpleMotion::new(100.0),
         Health::new(20.0),
+        Dash::new(96.0, 5.0),
+        PackRole::Ranged,
+        // Slow, heavy bolts, so ice mages take their time and lead their shots well
+        Marksmanship {
+            accuracy: 0.9,
+            reaction_secs: 0.6,
+        },
+        Reflexes::new(0.4, Evasion::Blink),
         Sprite::from_atlas_image(
             sprites.ice_mage_enemy_sprite_sheet.clone(),
             TextureAtlas {
@@ -219,6 +339,7 @@ fn ice_mage(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl
             },
         ),
         related!(Items[health_potion(sprites)]),
+        related!(GroundEffects[frost_field(sprites)]),
     )
 }
 
@@ -226,6 +347,14 @@ fn fire_mage(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> imp
     (
         SimpleMotion::new(150.0),
         Health::new(20.0),
+        Dash::new(96.0, 4.0),
+        PackRole::Ranged,
+        // Fire mages spray fast and loose
+        Marksmanship {
+            accuracy: 0.6,
+            reaction_secs: 0.3,
+        },
+        Reflexes::new(0.6, Evasion::Blink),
         Sprite::from_atlas_image(
             sprites.fire_mage_enemy_sprite_sheet.clone(),
             TextureAtlas {
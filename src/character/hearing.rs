@@ -0,0 +1,126 @@
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+use crate::{
+    character::vision::{TargetInfo, Targeting},
+    prelude::*,
+};
+
+/// Seconds between the player's footsteps
+const FOOTSTEP_INTERVAL_SECS: f32 = 0.35;
+/// Footsteps carry further the faster the player is going
+const FOOTSTEP_RADIUS_PER_SPEED: f32 = 0.45;
+/// Moving slower than this makes no sound at all
+const SILENT_SPEED: f32 = 20.0;
+const WEAPON_NOISE_RADIUS: f32 = 220.0;
+/// Noises heard within this fraction of their radius are investigated, further ones just turn heads
+const INVESTIGATE_FRACTION: f32 = 0.7;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, emit_footsteps.in_set(InGameSystems::Simulation))
+        .add_observer(emit_weapon_noise)
+        .add_observer(on_noise_heard);
+}
+
+/// A sound characters with `Hearing` can pick up if they are within `radius` of it
+#[derive(Event, Clone, Copy)]
+pub struct Noise {
+    pub position: Vec2,
+    pub radius: f32,
+    /// Faction of whoever made the noise, allies ignore each other's noise. Noises without a
+    /// faction (ex. a chest creaking open) get everyone's attention.
+    pub faction: Option<Faction>,
+}
+
+/// Lets a character notice noises, turning towards them or going to check them out
+#[derive(Component)]
+pub(super) struct Hearing {
+    /// Multiplies how far away noises can be heard from
+    pub sensitivity: f32,
+}
+
+impl Default for Hearing {
+    fn default() -> Self {
+        Self { sensitivity: 1.0 }
+    }
+}
+
+fn emit_footsteps(
+    mut commands: Commands,
+    player: Single<(&Transform, &LinearVelocity, &Faction), With<Player>>,
+    mut footstep_timer: Local<Option<Timer>>,
+    time: Res<Time>,
+) {
+    let (transform, velocity, faction) = *player;
+    let timer = footstep_timer
+        .get_or_insert_with(|| Timer::from_seconds(FOOTSTEP_INTERVAL_SECS, TimerMode::Repeating));
+
+    let speed = velocity.length();
+    if speed < SILENT_SPEED || !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    commands.trigger(Noise {
+        position: transform.translation.xy(),
+        radius: speed * FOOTSTEP_RADIUS_PER_SPEED,
+        faction: Some(*faction),
+    });
+}
+
+fn emit_weapon_noise(
+    used: On<UseEquipment>,
+    mut commands: Commands,
+    item_query: Query<&ItemOf>,
+    holder_query: Query<(&Transform, &Faction)>,
+) {
+    if let Ok(item_of) = item_query.get(used.entity)
+        && let Ok((transform, faction)) = holder_query.get(item_of.0)
+    {
+        commands.trigger(Noise {
+            position: transform.translation.xy(),
+            radius: WEAPON_NOISE_RADIUS,
+            faction: Some(*faction),
+        });
+    }
+}
+
+/// Idle characters that hear something turn to face it, and go look if it was close or loud enough
+fn on_noise_heard(
+    noise: On<Noise>,
+    mut listener_query: Query<
+        (
+            &Hearing,
+            &Transform,
+            &Faction,
+            &mut TargetInfo,
+            &mut FacingDirection,
+        ),
+        Without<Targeting>,
+    >,
+    relations: Res<FactionRelations>,
+) {
+    for (hearing, transform, faction, mut target_info, mut facing_direction) in &mut listener_query
+    {
+        if noise
+            .faction
+            .is_some_and(|noise_faction| !relations.is_hostile(noise_faction, *faction))
+        {
+            continue;
+        }
+
+        let offset = noise.position - transform.translation.xy();
+        let range = noise.radius * hearing.sensitivity;
+        let distance = offset.length();
+
+        if distance > range {
+            continue;
+        }
+
+        let turned = FacingDirection::from_vec2(&facing_direction, offset);
+        facing_direction.set_if_neq(turned);
+
+        if distance <= range * INVESTIGATE_FRACTION {
+            target_info.last_known_position = Some(noise.position);
+        }
+    }
+}
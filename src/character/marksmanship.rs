@@ -0,0 +1,159 @@
+use avian2d::prelude::*;
+use bevy::{ecs::entity_disabling::Disabled, prelude::*};
+use rand::{Rng, rng};
+
+use crate::{
+    character::vision::{TargetInfo, Targeting},
+    prelude::*,
+};
+
+/// Widest angle a shot can miss by, for a character with no accuracy at all
+const MAX_AIM_ERROR: f32 = 0.35;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, solve_aim.in_set(InGameSystems::Simulation))
+        .add_observer(reroll_aim_error);
+}
+
+/// How well a ranged AI shoots, set per archetype for difficulty tuning
+#[derive(Component, Clone)]
+#[require(AimSolution)]
+pub(super) struct Marksmanship {
+    /// 0.0 shoots at where the target is standing, 1.0 fully leads a moving target. Less accurate
+    /// characters also miss by a wider angle.
+    pub accuracy: f32,
+    /// How long the target has to be in a clear line of fire before the first shot
+    pub reaction_secs: f32,
+}
+
+/// Where a ranged AI should aim to hit its target, and whether it should fire at all
+#[derive(Component, Default)]
+pub(super) struct AimSolution {
+    /// Leads the target based on its velocity and the equipped weapon's projectile speed
+    pub direction: Option<Vec2>,
+    /// Line of sight to the target, with no ally in the way
+    pub clear_shot: bool,
+    /// Had a clear shot for at least the reaction time
+    pub ready: bool,
+    clear_secs: f32,
+    /// Angle the next shot is off by, rerolled after every shot
+    error: f32,
+}
+
+fn solve_aim(
+    mut shooter_query: Query<(
+        &Marksmanship,
+        &mut AimSolution,
+        &Transform,
+        &Faction,
+        &TargetInfo,
+        Option<&Targeting>,
+        Option<&Mainhand>,
+        Option<&Children>,
+    )>,
+    target_query: Query<(&Transform, Option<&LinearVelocity>)>,
+    weapon_query: Query<&Projectiles>,
+    projectile_query: Query<&Projectile, With<Disabled>>,
+    spatial_query: SpatialQuery,
+    relations: Res<FactionRelations>,
+    time: Res<Time>,
+) {
+    for (marksmanship, mut aim, transform, faction, target_info, targeting, mainhand, children) in
+        &mut shooter_query
+    {
+        let Some((target_transform, target_velocity)) =
+            targeting.and_then(|targeting| target_query.get(targeting.0).ok())
+        else {
+            aim.direction = None;
+            aim.clear_shot = false;
+            aim.ready = false;
+            aim.clear_secs = 0.0;
+            continue;
+        };
+
+        let position = transform.translation.xy();
+        let target_position = target_transform.translation.xy();
+        let target_velocity = target_velocity.map_or(Vec2::ZERO, |velocity| velocity.0);
+
+        let projectile_speed = mainhand
+            .and_then(|mainhand| weapon_query.get(mainhand.get()).ok())
+            .and_then(|projectiles| projectiles.iter().next())
+            .and_then(|projectile| projectile_query.get(projectile).ok())
+            .map(|projectile| projectile.speed);
+
+        let lead = projectile_speed
+            .and_then(|speed| intercept_time(target_position - position, target_velocity, speed))
+            .map_or(Vec2::ZERO, |time| {
+                target_velocity * time * marksmanship.accuracy
+            });
+
+        let direction = (target_position + lead - position)
+            .normalize_or(target_info.direction)
+            .rotate(Vec2::from_angle(aim.error));
+        aim.direction = Some(direction);
+
+        // Don't shoot allies in the back
+        let ally_filter = SpatialQueryFilter::from_mask(relations.friendly_hurtboxes(*faction))
+            .with_excluded_entities(children.into_iter().flat_map(RelationshipTarget::iter));
+        let ally_in_the_way = Dir2::new(direction).is_ok_and(|direction| {
+            spatial_query
+                .cast_ray(
+                    position,
+                    direction,
+                    target_info.distance,
+                    true,
+                    &ally_filter,
+                )
+                .is_some()
+        });
+
+        aim.clear_shot = target_info.line_of_sight && !ally_in_the_way;
+        aim.clear_secs = if aim.clear_shot {
+            aim.clear_secs + time.delta_secs()
+        } else {
+            0.0
+        };
+        aim.ready = aim.clear_shot && aim.clear_secs >= marksmanship.reaction_secs;
+    }
+}
+
+/// Seconds until a projectile fired now at `speed` can meet a target at `offset` moving with
+/// `velocity`, if it can catch up at all
+fn intercept_time(offset: Vec2, velocity: Vec2, speed: f32) -> Option<f32> {
+    let a = velocity.length_squared() - speed * speed;
+    let b = 2.0 * offset.dot(velocity);
+    let c = offset.length_squared();
+
+    if a.abs() < f32::EPSILON {
+        let time = -c / b;
+        return (time > 0.0).then_some(time);
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let root = discriminant.sqrt();
+    [(-b - root) / (2.0 * a), (-b + root) / (2.0 * a)]
+        .into_iter()
+        .filter(|time| *time > 0.0)
+        .min_by(f32::total_cmp)
+}
+
+fn reroll_aim_error(
+    used: On<UseEquipment>,
+    item_query: Query<&ItemOf>,
+    mut shooter_query: Query<(&Marksmanship, &mut AimSolution)>,
+) {
+    if let Ok(item_of) = item_query.get(used.entity)
+        && let Ok((marksmanship, mut aim)) = shooter_query.get_mut(item_of.0)
+    {
+        let max_error = (1.0 - marksmanship.accuracy).clamp(0.0, 1.0) * MAX_AIM_ERROR;
+        aim.error = if max_error > 0.0 {
+            rng().random_range(-max_error..max_error)
+        } else {
+            0.0
+        };
+    }
+}
@@ -1,18 +1,34 @@
 mod animation;
 mod behavior;
+mod behavior_tree;
+mod companion;
+mod dash;
 mod enemy;
+mod hearing;
+mod marksmanship;
 mod npc;
+mod pack;
 mod player;
+mod reflexes;
 mod state;
+mod threat;
 mod vision;
 
 pub mod prelude {
     pub use super::animation::*;
+    pub use super::behavior::Anchor;
+    pub use super::behavior_tree::{
+        BehaviorSource, BehaviorTreeAsset, Behaviors, RunningBehaviorNode,
+    };
+    pub use super::companion::{Companion, JoinParty};
+    pub use super::dash::*;
     pub use super::enemy::*;
+    pub use super::hearing::Noise;
     pub use super::npc::*;
+    pub use super::pack::PackRole;
     pub use super::player::prelude::*;
     pub use super::state::*;
-    pub use super::vision::Vision;
+    pub use super::vision::{TargetInfo, Vision};
 }
 
 use avian2d::prelude::*;
@@ -24,13 +40,31 @@ pub struct CharacterPlugin;
 
 impl Plugin for CharacterPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((player::plugin, enemy::plugin, npc::plugin));
+        app.add_plugins((
+            player::plugin,
+            enemy::plugin,
+            npc::plugin,
+            companion::plugin,
+        ));
 
-        app.add_plugins((animation::plugin, behavior::plugin, vision::plugin));
+        app.add_plugins((
+            animation::plugin,
+            behavior::plugin,
+            behavior_tree::plugin,
+            dash::plugin,
+            hearing::plugin,
+            marksmanship::plugin,
+            pack::plugin,
+            reflexes::plugin,
+            threat::plugin,
+            vision::plugin,
+        ));
 
         app.add_systems(
             FixedUpdate,
-            state::motion_to_velocity.in_set(MainSystems::InGame),
+            (state::motion_to_velocity, dash::dash_to_velocity)
+                .chain()
+                .in_set(MainSystems::InGame),
         );
     }
 }
@@ -44,8 +78,10 @@ impl Plugin for CharacterPlugin {
     NoAutoMass,
     CharacterAnimationState,
     Vision,
+    Poise,
     ItemCapacity(10),
     AnimationTimer,
+    NavPath,
     YSort::from_offset(CHARACTER_FEET_POS_OFFSET))]
 pub struct Character;
 
@@ -74,12 +110,12 @@ impl Purse {
         self.amount += amount;
     }
 
-    // pub fn remove(&mut self, amount: u32) -> Result<u32, String> {
-    //     if self.amount >= amount {
-    //         self.amount -= amount;
-    //         Ok(self.amount)
-    //     } else {
-    //         Err("Not enough in purse!".to_string())
-    //     }
-    // }
+    pub fn remove(&mut self, amount: u32) -> Result<u32, String> {
+        if self.amount >= amount {
+            self.amount -= amount;
+            Ok(self.amount)
+        } else {
+            Err("Not enough in purse!".to_string())
+        }
+    }
 }
@@ -1,6 +1,12 @@
 use bevy::prelude::*;
 
-use crate::prelude::{Menu, PlayerInteraction};
+use crate::{
+    character::{Purse, companion::MAX_COMPANIONS},
+    prelude::{Companion, JoinParty, Menu, Player, PlayerInteraction},
+};
+
+/// Gold it costs to have the helper join the party
+const HIRE_COST: u32 = 100;
 
 pub(super) fn on_shop_keeper_store_open(
     _: On<PlayerInteraction>,
@@ -16,6 +22,30 @@ pub(super) fn on_stat_trainer_store_open(
     next_menu_state.set(Menu::StatsShop);
 }
 
-pub(super) fn on_game_guide_start(_: On<PlayerInteraction>, mut _commands: Commands) {
-    warn!("on_game_guide_start");
+/// The helper leaves the hub to join the player's party, if they can afford it and have room
+pub(super) fn on_helper_hire(
+    interaction: On<PlayerInteraction>,
+    mut commands: Commands,
+    mut purse: Single<&mut Purse, With<Player>>,
+    companion_query: Query<(), With<Companion>>,
+    helper_query: Query<&Transform>,
+) -> Result {
+    if companion_query.iter().count() >= MAX_COMPANIONS {
+        info!("Party is already full");
+        return Ok(());
+    }
+
+    let helper_transform = helper_query.get(interaction.entity)?;
+
+    if let Err(error) = purse.remove(HIRE_COST) {
+        info!("Can't hire the helper for {} gold: {}", HIRE_COST, error);
+        return Ok(());
+    }
+
+    commands.trigger(JoinParty {
+        position: helper_transform.translation.xy(),
+    });
+    commands.entity(interaction.entity).despawn();
+
+    Ok(())
 }
@@ -1,19 +1,14 @@
+use avian2d::prelude::{RayCaster, SpatialQueryFilter};
 use bevy::{prelude::*, ui_widgets::observe};
 use bevy_behave::prelude::*;
 
 mod interaction;
 
 use crate::{
-    character::{
-        Character,
-        behavior::{Idle, Retreat},
-        physical_collider,
-    },
+    character::{Character, behavior::Anchor, physical_collider, vision::VisionCapabilities},
     prelude::*,
 };
 
-use super::behavior::{Anchor, Wander};
-
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(spawn_npcs)
         .add_observer(despawn_all::<CleanupZone, NPC>);
@@ -23,7 +18,7 @@ pub(super) fn plugin(app: &mut App) {
 pub struct SpawnNpcs(pub Vec<Vec2>);
 
 #[derive(Component)]
-#[require(Character)]
+#[require(Character, VisionCapabilities, Faction = Faction::Villager)]
 pub struct NPC;
 
 #[derive(Clone, Copy, Debug)]
@@ -42,6 +37,7 @@ fn spawn_npcs(
     sprites: Res<SpriteAssets>,
     sprite_layouts: Res<SpriteSheetLayouts>,
     shadows: Res<Shadows>,
+    behaviors: Behaviors,
 ) {
     // Define the NPC types we want to spawn in order
     let npc_types = [NPCType::Helper, NPCType::Shopkeeper, NPCType::StatTrainer];
@@ -56,6 +52,7 @@ fn spawn_npcs(
             &sprites,
             &sprite_layouts,
             &shadows,
+            behaviors.tree(&behaviors.trees.villager),
         );
     }
 }
@@ -67,12 +64,13 @@ fn spawn_npc(
     sprites: &SpriteAssets,
     sprite_layouts: &SpriteSheetLayouts,
     shadows: &Shadows,
+    behavior: Tree<Behave>,
 ) {
     match npc_type {
         NPCType::Helper => spawn_npc_with_equipment(
             commands,
             (
-                base_npc(spawn_position, shadows),
+                base_npc(spawn_position, shadows, behavior),
                 helper(sprites, sprite_layouts),
             ),
             ice_staff(sprites, sprite_layouts),
@@ -80,7 +78,7 @@ fn spawn_npc(
         NPCType::Shopkeeper => spawn_npc_with_equipment(
             commands,
             (
-                base_npc(spawn_position, shadows),
+                base_npc(spawn_position, shadows, behavior),
                 shopkeeper(sprites, sprite_layouts),
             ),
             axe(sprites),
@@ -88,7 +86,7 @@ fn spawn_npc(
         NPCType::StatTrainer => spawn_npc_with_equipment(
             commands,
             (
-                base_npc(spawn_position, shadows),
+                base_npc(spawn_position, shadows, behavior),
                 stat_trainer(sprites, sprite_layouts),
             ),
             sword(sprites),
@@ -107,22 +105,30 @@ fn spawn_npc_with_equipment(commands: &mut Commands, npc: impl Bundle, mainhand:
     });
 }
 
-fn base_npc(spawn_position: Vec2, shadows: &Shadows) -> impl Bundle {
+fn base_npc(spawn_position: Vec2, shadows: &Shadows, behavior: Tree<Behave>) -> impl Bundle {
     (
         NPC,
         Anchor::new(spawn_position, WANDER_RADIUS),
         SimpleMotion::new(100.0),
         Health::new(1000.0),
+        Mana::new(100.0, 10.0),
         Transform::from_translation(spawn_position.extend(ZLayer::OnGround.z())),
+        // Keep an eye out for anything hostile wandering into the hub
+        RayCaster::default()
+            .with_max_distance(300.0)
+            .with_query_filter(SpatialQueryFilter::from_mask(
+                GameCollisionLayer::HighObstacle,
+            ))
+            .with_max_hits(1),
         children![
             shadow(shadows, CHARACTER_FEET_POS_OFFSET - 4.0),
             (
                 InteractionZone::NPC,
                 Transform::from_xyz(0.0, CHARACTER_FEET_POS_OFFSET, 0.0),
             ),
-            hurtbox(Vec2::new(26.0, 42.0), GameCollisionLayer::AllyHurtBox),
+            hurtbox(Vec2::new(26.0, 42.0), Faction::Villager),
             physical_collider(),
-            BehaveTree::new(wander_and_retreat_behavior()),
+            BehaveTree::new(behavior),
         ],
     )
 }
@@ -136,7 +142,7 @@ fn helper(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl B
                 ..default()
             },
         ),
-        observe(interaction::on_game_guide_start),
+        observe(interaction::on_helper_hire),
     )
 }
 
@@ -165,17 +171,3 @@ fn stat_trainer(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) ->
         observe(interaction::on_stat_trainer_store_open),
     )
 }
-
-fn wander_and_retreat_behavior() -> Tree<Behave> {
-    behave! {
-        Behave::Forever => {
-            Behave::Fallback => {
-                Behave::Sequence => {
-                    Behave::spawn_named("Idle", Idle::default().timer_range(1.0..4.0)),
-                    Behave::spawn_named("Wander", Wander::builder().timer_range(1.0..2.5)),
-                },
-                Behave::spawn_named("Retreat", Retreat),
-            }
-        }
-    }
-}
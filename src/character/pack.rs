@@ -0,0 +1,166 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+    character::vision::{
+        TargetInfo, TargetLock, TargetedBy, Targeting, VisionCapabilities, Watching,
+    },
+    prelude::*,
+};
+
+/// Allies this close hear the alert when a character spots a target
+const ALERT_RADIUS: f32 = 300.0;
+/// How long alerted allies keep chasing the shared target without seeing it themselves
+const ALERT_LOCK_SECS: f32 = 4.0;
+/// How far from the target flankers spread out
+const FLANK_RADIUS: f32 = 56.0;
+/// Once this close, flankers stop circling and go straight for the target
+pub(super) const FLANK_ENGAGE_DISTANCE: f32 = 96.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (assign_pack_positions, tick_alerted).in_set(InGameSystems::Simulation),
+    )
+    .add_observer(on_target_acquired_alert)
+    .add_observer(on_target_lost_leave_pack);
+}
+
+/// How a character fights alongside allies going after the same target
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum PackRole {
+    /// Spreads around the target so the pack surrounds them
+    Flanker,
+    /// Holds back while flankers are engaged
+    Ranged,
+}
+
+/// Where a flanker should approach from, relative to the target
+#[derive(Component)]
+pub(super) struct FlankOffset(pub Vec2);
+
+/// Ranged pack members keep further away while a flanker is on the target
+#[derive(Component)]
+pub(super) struct HoldBack;
+
+/// Joined a fight because an ally raised the alert, doesn't alert anyone else until it wears off
+#[derive(Component)]
+struct Alerted(Timer);
+
+/// When a character spots a target, nearby allies that aren't busy join the chase
+fn on_target_acquired_alert(
+    targeting_added: On<Add, Targeting>,
+    mut commands: Commands,
+    alerter_query: Query<(&Targeting, &Transform, &Faction), Without<Alerted>>,
+    mut ally_query: Query<
+        (Entity, &Transform, &Faction, &mut TargetInfo),
+        (With<VisionCapabilities>, Without<Targeting>),
+    >,
+    target_query: Query<&Transform>,
+) {
+    let alerter = targeting_added.entity;
+
+    let Ok((targeting, alerter_transform, alerter_faction)) = alerter_query.get(alerter) else {
+        return;
+    };
+
+    let target = targeting.0;
+    let target_position = target_query
+        .get(target)
+        .map(|transform| transform.translation.xy())
+        .ok();
+
+    for (ally, ally_transform, ally_faction, mut target_info) in &mut ally_query {
+        if ally == alerter
+            || ally_faction != alerter_faction
+            || ally_transform
+                .translation
+                .xy()
+                .distance(alerter_transform.translation.xy())
+                > ALERT_RADIUS
+        {
+            continue;
+        }
+
+        debug!("{} alerted {} to {}", alerter, ally, target);
+
+        // If they never catch sight of the target, they'll search where it was when the alert went out
+        target_info.last_known_position = target_position;
+
+        commands.entity(ally).insert((
+            Alerted(Timer::from_seconds(ALERT_LOCK_SECS, TimerMode::Once)),
+            Watching(target),
+            Targeting(target),
+            TargetLock,
+        ));
+        schedule_component_removal::<TargetLock>(&mut commands, ally, ALERT_LOCK_SECS);
+    }
+}
+
+fn tick_alerted(
+    mut commands: Commands,
+    mut alerted_query: Query<(Entity, &mut Alerted)>,
+    time: Res<Time>,
+) {
+    for (entity, mut alerted) in &mut alerted_query {
+        if alerted.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<Alerted>();
+        }
+    }
+}
+
+fn on_target_lost_leave_pack(targeting_removed: On<Remove, Targeting>, mut commands: Commands) {
+    if let Ok(mut entity_commands) = commands.get_entity(targeting_removed.entity) {
+        entity_commands.try_remove::<(FlankOffset, HoldBack)>();
+    }
+}
+
+/// Spreads flankers evenly around each target, and holds ranged members back while a flanker is
+/// engaged
+fn assign_pack_positions(
+    mut commands: Commands,
+    target_query: Query<(&TargetedBy, &Transform)>,
+    member_query: Query<(&PackRole, &Transform)>,
+) {
+    for (targeted_by, target_transform) in &target_query {
+        let target_position = target_transform.translation.xy();
+
+        let mut flankers: Vec<(Entity, f32)> = Vec::new();
+        let mut ranged: Vec<Entity> = Vec::new();
+
+        for member in targeted_by.iter() {
+            match member_query.get(member) {
+                Ok((PackRole::Flanker, transform)) => {
+                    let angle = (transform.translation.xy() - target_position).to_angle();
+                    flankers.push((member, angle));
+                }
+                Ok((PackRole::Ranged, _)) => ranged.push(member),
+                Err(_) => {}
+            }
+        }
+
+        // Slots go around the target in the same order the flankers already stand in, so nobody
+        // has to cross over to the other side
+        flankers.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((_, first_angle)) = flankers.first().copied() {
+            let slot_angle = TAU / flankers.len() as f32;
+
+            for (i, (flanker, _)) in flankers.iter().enumerate() {
+                let angle = first_angle + slot_angle * i as f32;
+                commands
+                    .entity(*flanker)
+                    .insert(FlankOffset(Vec2::from_angle(angle) * FLANK_RADIUS));
+            }
+        }
+
+        for ranged_member in ranged {
+            if flankers.is_empty() {
+                commands.entity(ranged_member).remove::<HoldBack>();
+            } else {
+                commands.entity(ranged_member).insert(HoldBack);
+            }
+        }
+    }
+}
@@ -1,26 +1,95 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
 use bevy::prelude::*;
 
 use crate::prelude::*;
 
+/// Fraction of max health restored when the player is revived
+const REVIVE_HEALTH_FRACTION: f32 = 0.5;
+/// How long the player can't be hurt after being revived
+const REVIVE_INVULNERABLE_SECS: f32 = 2.0;
+/// Enemies this close get shoved away when the player gets back up
+const REVIVE_KNOCKBACK_RADIUS: f32 = 160.0;
+/// Impulse applied to shoved enemies, a 50 mass enemy gets launched at 600 pixels per second
+const REVIVE_KNOCKBACK_IMPULSE: f32 = 30000.0;
+/// How long shoved enemies lose control of their movement
+const REVIVE_HIT_STUN_SECS: f32 = 0.5;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         finish_death_animation
             .in_set(InGameSystems::Vfx)
             .run_if(in_state(PlayingState::Death)),
-    );
+    )
+    .add_observer(on_player_revived);
 }
 
 #[derive(Component)]
 struct GameOverTimer(Timer);
 
+/// Extra lives bought in the hub, only last for the current run
+#[derive(Component, Default)]
+pub struct ExtraLives(pub u32);
+
+/// Triggered when a player is saved from defeat by a revive token or an extra life
+#[derive(EntityEvent)]
+pub struct Revived {
+    pub entity: Entity,
+}
+
 pub(super) fn on_player_defeated(
     _: On<Defeated>,
-    player: Single<(Entity, &mut SimpleMotion), With<Player>>,
+    player: Single<
+        (
+            Entity,
+            &mut SimpleMotion,
+            &mut Health,
+            &mut ExtraLives,
+            Option<&Items>,
+        ),
+        With<Player>,
+    >,
+    revive_token_query: Query<(), With<ReviveToken>>,
     mut commands: Commands,
     mut playing_state: ResMut<NextState<PlayingState>>,
 ) {
-    let (player_entity, mut player_motion) = player.into_inner();
+    let (player_entity, mut player_motion, mut health, mut extra_lives, items) =
+        player.into_inner();
+
+    // Already revived by another hit that landed this frame
+    if health.hp > 0.0 {
+        return;
+    }
+
+    // Revive tokens found during the run are spent before extra lives
+    let revive_token =
+        items.and_then(|items| items.iter().find(|item| revive_token_query.contains(*item)));
+
+    let can_revive = if let Some(revive_token) = revive_token {
+        commands.entity(revive_token).despawn();
+        true
+    } else if extra_lives.0 > 0 {
+        extra_lives.0 -= 1;
+        true
+    } else {
+        false
+    };
+
+    if can_revive {
+        // Restored right away so any other defeat this frame is ignored
+        health.hp = health.max_hp * REVIVE_HEALTH_FRACTION;
+        commands.trigger(Healed {
+            entity: player_entity,
+            amount: health.hp,
+            healer: None,
+        });
+        commands.trigger(Revived {
+            entity: player_entity,
+        });
+        return;
+    }
 
     commands
         .entity(player_entity)
@@ -31,6 +100,38 @@ pub(super) fn on_player_defeated(
     playing_state.set(PlayingState::Death);
 }
 
+/// Gives the player a moment to recover by making them invulnerable and shoving nearby enemies away
+fn on_player_revived(
+    revived: On<Revived>,
+    mut commands: Commands,
+    mut player_query: Query<(&Transform, &mut IFrames), With<Player>>,
+    mut enemy_query: Query<(Entity, &Transform, Forces, Option<&KnockbackResistance>), With<Enemy>>,
+) {
+    let Ok((player_transform, mut iframes)) = player_query.get_mut(revived.entity) else {
+        return;
+    };
+
+    iframes.grant_for(Duration::from_secs_f32(REVIVE_INVULNERABLE_SECS));
+
+    let player_position = player_transform.translation.xy();
+
+    for (enemy, enemy_transform, mut forces, resistance) in &mut enemy_query {
+        let offset = enemy_transform.translation.xy() - player_position;
+
+        if offset.length() > REVIVE_KNOCKBACK_RADIUS {
+            continue;
+        }
+
+        let knockback_taken = 1.0 - resistance.map_or(0.0, |r| r.0.clamp(0.0, 1.0));
+        let direction = offset.try_normalize().unwrap_or(Vec2::Y);
+
+        forces.apply_linear_impulse(direction * REVIVE_KNOCKBACK_IMPULSE * knockback_taken);
+        commands
+            .entity(enemy)
+            .insert(HitStun::new(REVIVE_HIT_STUN_SECS * knockback_taken));
+    }
+}
+
 fn finish_death_animation(
     time: Res<Time>,
     player_death_timer_single: Single<&mut GameOverTimer, With<Player>>,
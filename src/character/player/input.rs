@@ -2,7 +2,11 @@ use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
 use crate::{
-    character::player::{AimInput, PlayerMovement, overlay::PlayerEquipmentUsed},
+    character::player::{
+        AimInput,
+        movement::{DashInput, PlayerMovement},
+        overlay::PlayerEquipmentUsed,
+    },
     prelude::*,
 };
 
@@ -11,6 +15,8 @@ pub(super) fn plugin(app: &mut App) {
 
     // Use Equipment Logic
     app.add_observer(on_use_mainhand_input)
+        .add_observer(on_charge_mainhand_input)
+        .add_observer(on_use_mainhand_release)
         .add_observer(on_use_offhand_input)
         .add_observer(on_use_offhand_complete);
 
@@ -25,6 +31,9 @@ pub(super) fn plugin(app: &mut App) {
 }
 
 const MOUSE_SENSITIVITY: f32 = 0.5;
+/// How long the mainhand input has to be held before a chargeable item starts charging, so quick
+/// taps still get used right away
+const CHARGE_HOLD_SECS: f32 = 0.15;
 const CONTROLLER_AIM_SENSITIVITY: f32 = 8.0;
 
 pub(super) fn player_actions() -> impl Bundle {
@@ -53,6 +62,10 @@ pub(super) fn player_actions() -> impl Bundle {
             Action::<PlayerInteractionInput>::new(),
             bindings![KeyCode::Space, GamepadButton::South],
         ),
+        (
+            Action::<DashInput>::new(),
+            bindings![KeyCode::ShiftLeft, GamepadButton::East],
+        ),
         (
             Action::<PlayerMovement>::new(),
             DeadZone::default(),
@@ -75,6 +88,11 @@ pub(super) fn player_actions() -> impl Bundle {
             Action::<UseMainhand>::new(),
             bindings![MouseButton::Left, GamepadButton::RightTrigger, GamepadButton::RightTrigger2],
         ),
+        (
+            Action::<ChargeMainhand>::new(),
+            Hold::new(CHARGE_HOLD_SECS),
+            bindings![MouseButton::Left, GamepadButton::RightTrigger, GamepadButton::RightTrigger2],
+        ),
         (
             Action::<UseOffhand>::new(),
             bindings![MouseButton::Right, GamepadButton::LeftTrigger, GamepadButton::LeftTrigger2],
@@ -130,22 +148,120 @@ struct UseMainhand;
 #[action_output(bool)]
 struct UseOffhand;
 
+/// Fires once `UseMainhand` has been held long enough to start charging
+#[derive(InputAction)]
+#[action_output(bool)]
+struct ChargeMainhand;
+
 fn on_use_mainhand_input(
     use_mainhand: On<Start<UseMainhand>>,
     mut commands: Commands,
-    player: Option<Single<(&Mainhand, Option<&mut Mana>), With<Player>>>,
+    player: Option<Single<(&Mainhand, Option<&mut Mana>, Option<&Statuses>), With<Player>>>,
     mut mainhand_query: Query<EquipmentUsed>,
+    stunned_query: Query<(), With<Stunned>>,
+    chargeable_query: Query<(), With<Chargeable>>,
+) {
+    // Chargeable items get used once the input is released instead
+    if let Some(player) = &player
+        && chargeable_query.contains(player.0.get())
+    {
+        return;
+    }
+
+    try_use_mainhand(
+        &mut commands,
+        use_mainhand.context,
+        player,
+        &mut mainhand_query,
+        &stunned_query,
+    );
+}
+
+fn on_charge_mainhand_input(
+    _: On<Fire<ChargeMainhand>>,
+    mut commands: Commands,
+    player: Option<Single<(&Mainhand, Option<&Statuses>), With<Player>>>,
+    chargeable_query: Query<&Chargeable, Without<Charging>>,
+    stunned_query: Query<(), With<Stunned>>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+
+    // Can't start charging again until the stun wears off
+    let (player_mainhand, statuses) = player.into_inner();
+    if is_stunned(statuses, &stunned_query) {
+        return;
+    }
+
+    if let Ok(chargeable) = chargeable_query.get(player_mainhand.get()) {
+        commands
+            .entity(player_mainhand.get())
+            .insert(Charging::new(chargeable));
+    }
+}
+
+fn on_use_mainhand_release(
+    use_mainhand: On<Complete<UseMainhand>>,
+    mut commands: Commands,
+    player: Option<Single<(&Mainhand, Option<&mut Mana>, Option<&Statuses>), With<Player>>>,
+    mut mainhand_query: Query<EquipmentUsed>,
+    stunned_query: Query<(), With<Stunned>>,
+    chargeable_query: Query<Option<&Charging>, With<Chargeable>>,
+) {
+    let Some(mainhand) = player.as_ref().map(|player| player.0.get()) else {
+        return;
+    };
+
+    let Ok(charging) = chargeable_query.get(mainhand) else {
+        return;
+    };
+
+    // Quick taps release with no charge at all
+    let charge = charging.map_or(0.0, Charging::fraction);
+    commands
+        .entity(mainhand)
+        .remove::<Charging>()
+        .insert(Charge(charge));
+
+    try_use_mainhand(
+        &mut commands,
+        use_mainhand.context,
+        player,
+        &mut mainhand_query,
+        &stunned_query,
+    );
+
+    // Successful uses spend the charge, don't leave it behind if the use failed
+    commands.entity(mainhand).try_remove::<Charge>();
+}
+
+fn try_use_mainhand(
+    commands: &mut Commands,
+    holder: Entity,
+    player: Option<Single<(&Mainhand, Option<&mut Mana>, Option<&Statuses>), With<Player>>>,
+    mainhand_query: &mut Query<EquipmentUsed>,
+    stunned_query: &Query<(), With<Stunned>>,
 ) {
     let Some(player) = player else {
         commands.trigger(EquipmentUseFailed {
-            holder: use_mainhand.context,
+            holder,
             slot: EquipmentSlot::Mainhand,
             reason: EquipmentUseFailure::NoneEquipped,
         });
         return;
     };
 
-    let (mainhand, mut mana) = player.into_inner();
+    let (mainhand, mut mana, statuses) = player.into_inner();
+
+    if is_stunned(statuses, stunned_query) {
+        commands.trigger(EquipmentUseFailed {
+            holder,
+            slot: EquipmentSlot::Mainhand,
+            reason: EquipmentUseFailure::Stunned,
+        });
+        return;
+    }
 
     let failure_reason = mainhand_query
         .get_mut(mainhand.get())
@@ -154,7 +270,7 @@ fn on_use_mainhand_input(
 
     if let Err(failure_reason) = failure_reason {
         commands.trigger(EquipmentUseFailed {
-            holder: use_mainhand.context,
+            holder,
             slot: EquipmentSlot::Mainhand,
             reason: failure_reason,
         });
@@ -171,8 +287,9 @@ fn on_use_mainhand_input(
 fn on_use_offhand_input(
     use_offhand: On<Start<UseOffhand>>,
     mut commands: Commands,
-    player: Option<Single<(&Offhand, Option<&mut Mana>), With<Player>>>,
+    player: Option<Single<(&Offhand, Option<&mut Mana>, Option<&Statuses>), With<Player>>>,
     mut offhand_query: Query<EquipmentUsed>,
+    stunned_query: Query<(), With<Stunned>>,
 ) {
     let Some(player) = player else {
         commands.trigger(EquipmentUseFailed {
@@ -183,7 +300,16 @@ fn on_use_offhand_input(
         return;
     };
 
-    let (offhand, mut mana) = player.into_inner();
+    let (offhand, mut mana, statuses) = player.into_inner();
+
+    if is_stunned(statuses, &stunned_query) {
+        commands.trigger(EquipmentUseFailed {
+            holder: use_offhand.context,
+            slot: EquipmentSlot::Offhand,
+            reason: EquipmentUseFailure::Stunned,
+        });
+        return;
+    }
 
     let failure_reason = offhand_query
         .get_mut(offhand.get())
@@ -9,6 +9,7 @@ mod progression;
 
 pub mod prelude {
     pub use super::aim::PlayerAim;
+    pub use super::death::{ExtraLives, Revived};
     pub use super::interact::*;
     pub use super::progression::GameProgress;
     pub use super::{DisplayableStatType, Player, PlayerStats};
@@ -24,7 +25,6 @@ use crate::{
         player::{
             aim::{AimInput, player_aim},
             input::player_actions,
-            movement::PlayerMovement,
         },
     },
     prelude::*,
@@ -65,11 +65,16 @@ pub(super) fn plugin(app: &mut App) {
     Character,
     ItemCapacity(50),
     Health::new(100.0),
+    // Sturdier than the default so a single solid hit doesn't stagger the player
+    Poise::new(30.0, 10.0),
     SimpleMotion::new(250.0),
     // Double the mass of npcs/enemies so the player can push them around more
     Mass(100.0),
     IFrames,
-    Purse
+    ExtraLives,
+    Dash,
+    Purse,
+    Faction = Faction::Player
 )]
 pub struct Player {
     current_level: u32,
@@ -190,13 +195,17 @@ fn spawn_player(
             ),
             related!(Items[
                 ice_staff(&sprites, &sprite_layouts),
+                arcane_staff(&sprites, &sprite_layouts),
+                meteor_staff(&sprites, &sprite_layouts),
                 sword(&sprites),
                 axe(&sprites),
                 freeze_axe(&sprites),
                 magic_shield(&sprites, &sprite_layouts),
                 knight_shield(&sprites, &sprite_layouts),
                 health_potion(&sprites),
-                tome_of_healing(&sprites)
+                stoneskin_potion(&sprites),
+                tome_of_healing(&sprites),
+                tome_of_sanctuary(&sprites, &sprite_layouts)
             ]),
             observe(death::on_player_defeated),
             observe(overlay::on_equipment_use_failed),
@@ -204,7 +213,7 @@ fn spawn_player(
                 player_aim(gizmo_assets),
                 shadow(&shadows, CHARACTER_FEET_POS_OFFSET - 4.0),
                 physical_collider(),
-                hurtbox(Vec2::new(26.0, 42.0), GameCollisionLayer::AllyHurtBox),
+                hurtbox(Vec2::new(26.0, 42.0), Faction::Player),
                 (
                     PlayerInteractionRadius,
                     Transform::from_xyz(0.0, CHARACTER_FEET_POS_OFFSET, 0.0),
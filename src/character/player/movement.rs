@@ -6,7 +6,8 @@ use bevy_enhanced_input::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(on_player_stopped)
-        .add_observer(on_player_movement);
+        .add_observer(on_player_movement)
+        .add_observer(on_dash_input);
 }
 
 #[derive(InputAction)]
@@ -26,3 +27,27 @@ fn on_player_stopped(
 ) {
     player_motion.stop_moving();
 }
+
+#[derive(InputAction)]
+#[action_output(bool)]
+pub(super) struct DashInput;
+
+fn on_dash_input(
+    dash: On<Start<DashInput>>,
+    mut commands: Commands,
+    player: Single<(&SimpleMotion, &Vision), With<Player>>,
+) {
+    let (motion, vision) = player.into_inner();
+
+    // Dash where we're moving, or where we're aiming if standing still
+    let direction = if motion.is_moving() {
+        motion.direction
+    } else {
+        vision.aim_direction
+    };
+
+    commands.trigger(AttemptDash {
+        entity: dash.context,
+        direction,
+    });
+}
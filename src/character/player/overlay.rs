@@ -9,13 +9,15 @@ pub(super) fn plugin(app: &mut App) {
             update_exp_bar,
             update_action_bar,
             update_cooldowns,
+            update_charge_meters,
             (update_mana_bar, update_lost_mana_bar).chain(),
             (update_health_bar, update_lost_health_bar).chain(),
         )
             .in_set(InGameSystems::HudOverlay),
     );
 
-    app.add_observer(on_equipment_use_add_cooldown_line);
+    app.add_observer(on_equipment_use_add_cooldown_line)
+        .add_observer(on_dash_add_cooldown_line);
 
     app.add_observer(despawn_all::<RestartEvent, PlayerOverlay>);
 }
@@ -280,9 +282,19 @@ pub(super) struct ActionBox {
     slot: EquipmentSlot,
 }
 
+/// Action box showing the dash cooldown, it has no equipment slot
+#[derive(Component)]
+struct DashBox;
+
 #[derive(Component)]
 struct CooldownIndicator;
 
+/// Fills up along the bottom of an action box while its equipment is being charged
+#[derive(Component)]
+struct ChargeMeter {
+    slot: EquipmentSlot,
+}
+
 #[derive(Component)]
 #[require(Lifespan::new(0.1))]
 pub(super) struct ErrorFlash;
@@ -294,6 +306,8 @@ const ACTION_BOX_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.8); // 80% opaque
 const ACTION_BOX_OUTLINE_COLOR: Color = Color::srgba(0.8, 0.8, 0.8, 0.5); // Semi-transparent white
 const COOLDOWN_LINE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.6); // Semi-transparent white
 const ERROR_FLASH_COLOR: Color = Color::srgba(0.9, 0.2, 0.2, 0.2); // Semi-transparent red
+const CHARGE_METER_COLOR: Color = Color::srgb(1.0, 0.6, 0.1);
+const CHARGE_METER_HEIGHT: f32 = 6.0;
 
 fn action_bar() -> impl Bundle {
     (
@@ -302,36 +316,76 @@ fn action_bar() -> impl Bundle {
             flex_direction: FlexDirection::Row,
             ..default()
         },
-        Children::spawn(SpawnIter(
-            [EquipmentSlot::Mainhand, EquipmentSlot::Offhand]
-                .iter()
-                .map(|slot| action_box(*slot)),
+        Children::spawn((
+            SpawnIter(
+                [EquipmentSlot::Mainhand, EquipmentSlot::Offhand]
+                    .iter()
+                    .map(|slot| action_box(*slot)),
+            ),
+            Spawn(dash_box()),
         )),
     )
 }
 
-fn action_box(slot: EquipmentSlot) -> impl Bundle {
+fn dash_box() -> impl Bundle {
     (
-        ActionBox { slot },
+        DashBox,
         Node {
             width: px(ACTION_BOX_SIZE),
             height: px(ACTION_BOX_SIZE),
             border: px(ACTION_BOX_BORDER).all(),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
             ..default()
         },
         BackgroundColor::from(ACTION_BOX_COLOR),
         BorderColor::from(ACTION_BOX_OUTLINE_COLOR),
         Children::spawn_one((
-            ImageNode::default(),
-            Node {
-                width: percent(100.0),
-                height: percent(100.0),
+            Text::new("Dash"),
+            TextFont {
+                font_size: 14.0,
                 ..default()
             },
         )),
     )
 }
 
+fn action_box(slot: EquipmentSlot) -> impl Bundle {
+    (
+        ActionBox { slot },
+        Node {
+            width: px(ACTION_BOX_SIZE),
+            height: px(ACTION_BOX_SIZE),
+            border: px(ACTION_BOX_BORDER).all(),
+            ..default()
+        },
+        BackgroundColor::from(ACTION_BOX_COLOR),
+        BorderColor::from(ACTION_BOX_OUTLINE_COLOR),
+        children![
+            (
+                ImageNode::default(),
+                Node {
+                    width: percent(100.0),
+                    height: percent(100.0),
+                    ..default()
+                },
+            ),
+            (
+                ChargeMeter { slot },
+                Node {
+                    width: px(0.0),
+                    height: px(CHARGE_METER_HEIGHT),
+                    position_type: PositionType::Absolute,
+                    left: px(0.0),
+                    bottom: px(0.0),
+                    ..default()
+                },
+                BackgroundColor::from(CHARGE_METER_COLOR),
+            )
+        ],
+    )
+}
+
 fn update_action_bar(
     action_box_query: Query<(&ActionBox, &Children)>,
     mut image_query: Query<&mut ImageNode>,
@@ -390,24 +444,43 @@ fn on_equipment_use_add_cooldown_line(
             .iter()
             .find(|(_, action_box)| action_box.slot == equipmemnt.slot)
     {
-        commands.entity(box_entity).with_children(|parent| {
-            parent.spawn((
-                CooldownIndicator,
-                Node {
-                    width: px(ACTION_BOX_INTERIOR_SIZE),
-                    height: px(ACTION_BOX_INTERIOR_SIZE),
-                    position_type: PositionType::Absolute,
-                    left: px(0.0),
-                    top: px(0.0),
-                    ..default()
-                },
-                Lifespan::new(equipmemnt.use_rate.remaining_secs()),
-                BackgroundColor::from(COOLDOWN_LINE_COLOR),
-            ));
-        });
+        commands
+            .entity(box_entity)
+            .with_child(cooldown_indicator(equipmemnt.use_rate.remaining_secs()));
+    }
+}
+
+fn on_dash_add_cooldown_line(
+    dashed: On<Dashed>,
+    mut commands: Commands,
+    player: Single<(Entity, &Dash), With<Player>>,
+    dash_box: Single<Entity, With<DashBox>>,
+) {
+    let (player, dash) = player.into_inner();
+
+    if dashed.entity == player {
+        commands
+            .entity(*dash_box)
+            .with_child(cooldown_indicator(dash.cooldown.remaining_secs()));
     }
 }
 
+fn cooldown_indicator(cooldown_secs: f32) -> impl Bundle {
+    (
+        CooldownIndicator,
+        Node {
+            width: px(ACTION_BOX_INTERIOR_SIZE),
+            height: px(ACTION_BOX_INTERIOR_SIZE),
+            position_type: PositionType::Absolute,
+            left: px(0.0),
+            top: px(0.0),
+            ..default()
+        },
+        Lifespan::new(cooldown_secs),
+        BackgroundColor::from(COOLDOWN_LINE_COLOR),
+    )
+}
+
 pub(super) fn on_equipment_use_failed(
     equipment_use_failed: On<EquipmentUseFailed>,
     mut commands: Commands,
@@ -439,6 +512,31 @@ fn update_cooldowns(mut cooldown_query: Query<(&mut Node, &Lifespan), With<Coold
     }
 }
 
+fn update_charge_meters(
+    mut charge_meter_query: Query<(&mut Node, &ChargeMeter)>,
+    player: Option<Single<(Option<&Mainhand>, Option<&Offhand>), With<Player>>>,
+    charging_query: Query<&Charging>,
+) {
+    let Some(player) = player else {
+        return;
+    };
+
+    let (mainhand, offhand) = player.into_inner();
+
+    for (mut meter_node, charge_meter) in &mut charge_meter_query {
+        let equipment = match charge_meter.slot {
+            EquipmentSlot::Mainhand => mainhand.map(Mainhand::get),
+            EquipmentSlot::Offhand => offhand.map(Offhand::get),
+        };
+
+        let charge = equipment
+            .and_then(|entity| charging_query.get(entity).ok())
+            .map_or(0.0, Charging::fraction);
+
+        meter_node.width = px(ACTION_BOX_INTERIOR_SIZE * charge);
+    }
+}
+
 fn get_action_bar_sprite(sprite: &Sprite) -> Sprite {
     match &sprite.texture_atlas {
         Some(atlas) => Sprite {
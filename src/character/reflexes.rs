@@ -0,0 +1,272 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::{Rng, rng};
+
+use crate::prelude::*;
+
+/// Projectiles only count as incoming if they will pass this close
+const PROJECTILE_DANGER_RADIUS: f32 = 32.0;
+/// How far ahead a character can see a projectile coming, in seconds until it arrives
+const PROJECTILE_WARNING_SECS: f32 = 0.6;
+/// Melee attackers winding up within this distance are a threat
+const MELEE_WARNING_DISTANCE: f32 = 110.0;
+/// How directly a melee attacker has to be aiming at the character to be a threat
+const MELEE_WARNING_DOT: f32 = 0.7;
+/// Reaction delay of the least and most skilled characters
+const SLOWEST_REACTION_SECS: f32 = 0.45;
+const FASTEST_REACTION_SECS: f32 = 0.08;
+/// Time between reactions, noticed or not, so a character doesn't dodge everything in a volley
+const REFLEX_COOLDOWN_SECS: f32 = 1.5;
+const STRAFE_DISTANCE: f32 = 56.0;
+const STRAFE_SECS: f32 = 0.18;
+const REACTIVE_BLOCK_SECS: f32 = 0.8;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (perceive_attacks, react_to_attacks, lower_reactive_block)
+            .chain()
+            .in_set(InGameSystems::Simulation),
+    );
+}
+
+/// How a character gets out of the way of an attack it sees coming
+#[derive(Clone, Copy, PartialEq)]
+pub(super) enum Evasion {
+    /// Quick sidestep out of the attack's path
+    Strafe,
+    /// Raises the offhand shield towards the attack
+    Block,
+    /// Dashes away, using the character's `Dash`
+    Blink,
+}
+
+/// Lets a character notice incoming projectiles and melee wind-ups and react to them. Skill is
+/// tuned per enemy, so tougher enemies can be smarter rather than just beefier.
+#[derive(Component, Clone)]
+pub(super) struct Reflexes {
+    /// Chance to notice an attack, and how quickly the reaction comes. 0.0 never reacts, 1.0
+    /// always reacts at the fastest reaction time.
+    pub skill: f32,
+    pub evasion: Evasion,
+    cooldown: Timer,
+}
+
+impl Reflexes {
+    pub fn new(skill: f32, evasion: Evasion) -> Self {
+        let mut cooldown = Timer::from_seconds(REFLEX_COOLDOWN_SECS, TimerMode::Once);
+        cooldown.set_elapsed(cooldown.duration());
+
+        Self {
+            skill: skill.clamp(0.0, 1.0),
+            evasion,
+            cooldown,
+        }
+    }
+
+    fn reaction_secs(&self) -> f32 {
+        SLOWEST_REACTION_SECS.lerp(FASTEST_REACTION_SECS, self.skill)
+    }
+}
+
+/// An attack the character noticed, reacted to once the delay runs out
+#[derive(Component)]
+struct Reacting {
+    delay: Timer,
+    /// Which way to get out of the attack's path
+    escape: Vec2,
+}
+
+/// Shield raised in reaction to an attack, lowered again when the timer runs out
+#[derive(Component)]
+struct ReactiveBlock(Timer);
+
+fn perceive_attacks(
+    mut commands: Commands,
+    mut character_query: Query<
+        (
+            Entity,
+            &mut Reflexes,
+            &Transform,
+            &Faction,
+            Option<&Statuses>,
+        ),
+        (Without<Reacting>, Without<Dashing>, Without<HitStun>),
+    >,
+    projectile_query: Query<(&Transform, &LinearVelocity, &Faction), With<Projectile>>,
+    attacker_query: Query<(
+        &Transform,
+        &Faction,
+        &Vision,
+        &AttackState,
+        Option<&Mainhand>,
+    )>,
+    charging_query: Query<(), With<Charging>>,
+    stunned_query: Query<(), With<Stunned>>,
+    relations: Res<FactionRelations>,
+    time: Res<Time>,
+) {
+    let mut rng = rng();
+
+    for (entity, mut reflexes, transform, faction, statuses) in &mut character_query {
+        if !reflexes.cooldown.tick(time.delta()).is_finished()
+            || is_stunned(statuses, &stunned_query)
+        {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+
+        let incoming_projectile = projectile_query.iter().find_map(
+            |(projectile_transform, velocity, projectile_faction)| {
+                if !relations.is_hostile(*projectile_faction, *faction) {
+                    return None;
+                }
+
+                let offset = position - projectile_transform.translation.xy();
+                let speed_squared = velocity.length_squared();
+                if speed_squared <= 0.0 {
+                    return None;
+                }
+
+                // Closest the projectile gets to us along its current path
+                let time_to_closest = offset.dot(velocity.0) / speed_squared;
+                let miss_by = offset - velocity.0 * time_to_closest;
+
+                ((0.0..PROJECTILE_WARNING_SECS).contains(&time_to_closest)
+                    && miss_by.length() < PROJECTILE_DANGER_RADIUS)
+                    .then(|| {
+                        // Keep going the way we're already off the path
+                        let side = velocity.0.perp().normalize_or_zero();
+                        if side.dot(miss_by) >= 0.0 {
+                            side
+                        } else {
+                            -side
+                        }
+                    })
+            },
+        );
+
+        let incoming_swing = || {
+            attacker_query.iter().find_map(
+                |(attacker_transform, attacker_faction, vision, attack_state, mainhand)| {
+                    let offset = position - attacker_transform.translation.xy();
+                    let away = offset.normalize_or_zero();
+
+                    let winding_up = attack_state.is_attacking
+                        || mainhand.is_some_and(|m| charging_query.contains(m.get()));
+                    let aimed_at_us = vision.aim_direction.dot(away) > MELEE_WARNING_DOT;
+
+                    (winding_up
+                        && aimed_at_us
+                        && offset.length() < MELEE_WARNING_DISTANCE
+                        && relations.is_hostile(*attacker_faction, *faction))
+                    .then_some(away)
+                },
+            )
+        };
+
+        let escape = match incoming_projectile {
+            Some(escape) => escape,
+            None => match incoming_swing() {
+                // Blinkers get out of reach, everyone else steps to the side
+                Some(away) if reflexes.evasion == Evasion::Blink => away,
+                Some(away) if rng.random_bool(0.5) => away.perp(),
+                Some(away) => -away.perp(),
+                None => continue,
+            },
+        };
+
+        reflexes.cooldown.reset();
+
+        if rng.random_range(0.0..1.0) < reflexes.skill {
+            commands.entity(entity).insert(Reacting {
+                delay: Timer::from_seconds(reflexes.reaction_secs(), TimerMode::Once),
+                escape,
+            });
+        }
+    }
+}
+
+fn react_to_attacks(
+    mut commands: Commands,
+    mut reacting_query: Query<(
+        Entity,
+        &mut Reacting,
+        &Reflexes,
+        &Transform,
+        Option<&Offhand>,
+        Option<&Statuses>,
+        Has<HitStun>,
+    )>,
+    shield_query: Query<(), With<Shield>>,
+    stunned_query: Query<(), With<Stunned>>,
+    navigation: Navigation,
+    time: Res<Time>,
+) {
+    for (entity, mut reacting, reflexes, transform, offhand, statuses, hit_stunned) in
+        &mut reacting_query
+    {
+        // Getting stunned before the reaction comes makes the character miss its chance
+        if hit_stunned || is_stunned(statuses, &stunned_query) {
+            commands.entity(entity).remove::<Reacting>();
+            continue;
+        }
+
+        if !reacting.delay.tick(time.delta()).is_finished() {
+            continue;
+        }
+
+        commands.entity(entity).remove::<Reacting>();
+
+        // Don't dodge into a wall
+        let escape = navigation.open_direction(transform.translation.xy(), reacting.escape);
+
+        match reflexes.evasion {
+            Evasion::Strafe => {
+                commands
+                    .entity(entity)
+                    .insert(Dashing::new(escape, STRAFE_DISTANCE, STRAFE_SECS));
+            }
+            Evasion::Block => {
+                if let Some(offhand) = offhand
+                    && shield_query.contains(offhand.get())
+                {
+                    commands.trigger(AIUseEquipment {
+                        entity: offhand.get(),
+                    });
+                    commands
+                        .entity(entity)
+                        .insert(ReactiveBlock(Timer::from_seconds(
+                            REACTIVE_BLOCK_SECS,
+                            TimerMode::Once,
+                        )));
+                }
+            }
+            Evasion::Blink => {
+                commands.trigger(AttemptDash {
+                    entity,
+                    direction: escape,
+                });
+            }
+        }
+    }
+}
+
+fn lower_reactive_block(
+    mut commands: Commands,
+    mut block_query: Query<(Entity, &mut ReactiveBlock, Option<&Offhand>)>,
+    time: Res<Time>,
+) {
+    for (entity, mut block, offhand) in &mut block_query {
+        if block.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<ReactiveBlock>();
+
+            if let Some(offhand) = offhand {
+                commands.trigger(StopUsingEquipment {
+                    entity: offhand.get(),
+                });
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use avian2d::prelude::LinearVelocity;
 use bevy::prelude::*;
 
+use crate::prelude::HitStun;
+
 /// Simple motion has no acceleration and assumes all entities move at max speed unless altered by `slowed_percentage`
 /// by Movement
 #[derive(Component, Clone)]
@@ -67,7 +69,10 @@ impl Default for SimpleMotion {
 }
 
 /// Converts simulation motion into physics "real" motion (using avian linear velocity)
-pub(super) fn motion_to_velocity(mut query: Query<(&SimpleMotion, &mut LinearVelocity)>) {
+/// Characters in hit-stun are left alone so knockback can play out
+pub(super) fn motion_to_velocity(
+    mut query: Query<(&SimpleMotion, &mut LinearVelocity), Without<HitStun>>,
+) {
     for (motion, mut velocity) in &mut query {
         if motion.is_moving() {
             let temp_vel = motion.get_velocity();
@@ -114,4 +119,6 @@ impl FacingDirection {
 #[derive(Component, Clone, Default)]
 pub struct AttackState {
     pub is_attacking: bool,
+    /// Which step of their weapon's combo the character is performing
+    pub combo_step: usize,
 }
@@ -0,0 +1,189 @@
+use avian2d::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::{
+    character::vision::{TargetInfo, Targeting},
+    prelude::*,
+};
+
+/// Threat gained per point of damage dealt
+pub(super) const DAMAGE_THREAT: f32 = 1.0;
+/// Threat gained per point of health healed on someone the character is already fighting
+const HEALING_THREAT: f32 = 0.5;
+/// Hostile characters closer than this build up threat just by standing there
+const PROXIMITY_RADIUS: f32 = 150.0;
+/// Threat per second gained by a hostile character right next to us, falls off with distance
+const PROXIMITY_THREAT_PER_SEC: f32 = 3.0;
+/// Fraction of threat lost per second
+const THREAT_DECAY_PER_SEC: f32 = 0.1;
+/// Entries below this are forgotten
+const MIN_THREAT: f32 = 0.1;
+/// A new target needs this much more threat than the current one to steal its attention
+const TARGET_SWITCH_RATIO: f32 = 1.2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (add_proximity_threat, decay_threat, select_threat_target)
+            .chain()
+            .in_set(InGameSystems::Simulation),
+    )
+    .add_observer(on_healed_threat);
+}
+
+/// How much each hostile entity has earned this character's attention. The highest-threat entity
+/// it can see becomes its target.
+#[derive(Component, Default)]
+pub(super) struct ThreatTable {
+    threat: HashMap<Entity, f32>,
+}
+
+impl ThreatTable {
+    pub fn add(&mut self, entity: Entity, amount: f32) {
+        *self.threat.entry(entity).or_default() += amount;
+    }
+
+    pub fn get(&self, entity: Entity) -> f32 {
+        self.threat.get(&entity).copied().unwrap_or_default()
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.threat.contains_key(&entity)
+    }
+
+    /// Entries sorted from highest to lowest threat
+    fn by_threat(&self) -> Vec<(Entity, f32)> {
+        let mut entries: Vec<(Entity, f32)> = self.threat.iter().map(|(e, t)| (*e, *t)).collect();
+        entries.sort_by(|a, b| b.1.total_cmp(&a.1));
+        entries
+    }
+}
+
+/// Healing someone a character is fighting makes the healer a threat too
+fn on_healed_threat(
+    heal_received: On<Healed>,
+    mut table_query: Query<(&mut ThreatTable, &Faction)>,
+    faction_query: Query<&Faction>,
+    relations: Res<FactionRelations>,
+) {
+    let healer = heal_received.healer.unwrap_or(heal_received.entity);
+
+    let Ok(healer_faction) = faction_query.get(healer) else {
+        return;
+    };
+
+    for (mut table, faction) in &mut table_query {
+        if table.contains(heal_received.entity) && relations.is_hostile(*faction, *healer_faction) {
+            table.add(healer, heal_received.amount * HEALING_THREAT);
+        }
+    }
+}
+
+fn add_proximity_threat(
+    mut table_query: Query<(Entity, &mut ThreatTable, &Transform, &Faction)>,
+    character_query: Query<(Entity, &Transform, &Faction), With<Health>>,
+    relations: Res<FactionRelations>,
+    time: Res<Time>,
+) {
+    for (entity, mut table, transform, faction) in &mut table_query {
+        for (other, other_transform, other_faction) in &character_query {
+            if other == entity || !relations.is_hostile(*faction, *other_faction) {
+                continue;
+            }
+
+            let distance = transform
+                .translation
+                .xy()
+                .distance(other_transform.translation.xy());
+
+            if distance < PROXIMITY_RADIUS {
+                let closeness = 1.0 - distance / PROXIMITY_RADIUS;
+                table.add(
+                    other,
+                    PROXIMITY_THREAT_PER_SEC * closeness * time.delta_secs(),
+                );
+            }
+        }
+    }
+}
+
+fn decay_threat(
+    mut table_query: Query<&mut ThreatTable>,
+    alive_query: Query<(), With<Health>>,
+    time: Res<Time>,
+) {
+    let decay = 1.0 - (THREAT_DECAY_PER_SEC * time.delta_secs()).min(1.0);
+
+    for mut table in &mut table_query {
+        table.threat.retain(|entity, threat| {
+            *threat *= decay;
+            *threat > MIN_THREAT && alive_query.contains(*entity)
+        });
+    }
+}
+
+/// Targets the highest-threat entity in sight, switching away from the current target only if the
+/// new one is clearly more threatening
+fn select_threat_target(
+    mut commands: Commands,
+    table_query: Query<(
+        Entity,
+        &ThreatTable,
+        &Transform,
+        &RayCaster,
+        &TargetInfo,
+        Option<&Targeting>,
+    )>,
+    target_query: Query<&Transform>,
+    spatial_query: SpatialQuery,
+) {
+    let wall_filter = SpatialQueryFilter::from_mask(GameCollisionLayer::HighObstacle);
+
+    for (entity, table, transform, ray_caster, target_info, targeting) in &table_query {
+        let current_target = targeting.map(|targeting| targeting.0);
+        let position = transform.translation.xy();
+
+        let in_sight = |candidate: Entity| {
+            if Some(candidate) == current_target {
+                return target_info.line_of_sight;
+            }
+
+            let Ok(candidate_transform) = target_query.get(candidate) else {
+                return false;
+            };
+
+            let offset = candidate_transform.translation.xy() - position;
+            let distance = offset.length();
+
+            distance <= ray_caster.max_distance
+                && Dir2::new(offset).is_ok_and(|direction| {
+                    spatial_query
+                        .cast_ray(position, direction, distance, true, &wall_filter)
+                        .is_none()
+                })
+        };
+
+        let Some((best, best_threat)) = table
+            .by_threat()
+            .into_iter()
+            .find(|(candidate, _)| in_sight(*candidate))
+        else {
+            continue;
+        };
+
+        let should_switch = match current_target {
+            Some(current) => {
+                best != current && best_threat > table.get(current) * TARGET_SWITCH_RATIO
+            }
+            None => true,
+        };
+
+        if should_switch {
+            debug!(
+                "{} switching target to {} (threat {})",
+                entity, best, best_threat
+            );
+            commands.entity(entity).insert(Targeting(best));
+        }
+    }
+}
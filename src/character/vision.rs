@@ -3,20 +3,37 @@ use std::f32::consts::FRAC_PI_4;
 use avian2d::prelude::{RayCaster, RayHits};
 use bevy::prelude::*;
 
-use crate::prelude::*;
+use crate::{
+    character::{
+        marksmanship::AimSolution,
+        threat::{DAMAGE_THREAT, ThreatTable},
+    },
+    prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Vision + Perception
     app.add_systems(
         Update,
-        (update_aim_position, update_target_info, is_target_in_sight)
+        (
+            update_vision_filters,
+            update_aim_position,
+            update_target_info,
+            is_target_in_sight,
+        )
             .in_set(InGameSystems::Simulation),
     );
 
     // Targeting
     app.add_systems(
         Update,
-        (should_target_watched, should_stop_targeting).in_set(InGameSystems::Simulation),
+        (
+            watch_nearest_hostile,
+            should_target_watched,
+            should_stop_targeting,
+            remember_target_position,
+        )
+            .in_set(InGameSystems::Simulation),
     )
     .add_observer(on_damage_aggro);
 }
@@ -52,7 +69,7 @@ impl Default for VisionCapabilities {
 
 /// Stores calculated perception data for a given potential or current target.
 #[derive(Component, Default)]
-pub(super) struct TargetInfo {
+pub struct TargetInfo {
     /// Distance to the observed entity.
     pub distance: f32,
     /// Direction vector pointing to the observed entity.
@@ -61,7 +78,9 @@ pub(super) struct TargetInfo {
     pub line_of_sight: bool,
     /// Whether the observed entity is within the entity’s vision cone angle.
     pub in_vision_cone: bool,
-    // TODO: Add `last_known_position` for memory/prediction systems.
+    /// Where the target was last seen, kept after losing sight of them so they can be searched for.
+    /// Cleared once the search is given up.
+    pub last_known_position: Option<Vec2>,
 }
 
 /// Marks that the entity is currently targeting another entity.
@@ -75,7 +94,8 @@ pub(super) struct Targeting(pub Entity);
 #[relationship_target(relationship = Targeting)]
 pub(super) struct TargetedBy(Vec<Entity>);
 
-/// Tracks which entity the NPC is currently watching or trying to detect.
+/// Tracks which entity the NPC is currently watching or trying to detect. Picked automatically
+/// as the nearest hostile character while the NPC isn't targeting anyone.
 #[derive(Component)]
 #[relationship(relationship_target = WatchedBy)]
 pub(super) struct Watching(pub Entity);
@@ -88,30 +108,50 @@ pub(super) struct WatchedBy(Vec<Entity>);
 /// Indicates a temporary target lock.
 /// Automatically removed after a certain duration via `Lifespan`.
 #[derive(Component)]
-struct TargetLock;
+pub(super) struct TargetLock;
 
 // ---------------------
 // VISION + PERCEPTION
 // ---------------------
 
+/// Vision rays are blocked by walls, and see the hurtboxes of anyone the character is hostile to
+fn update_vision_filters(
+    mut vision_query: Query<(Ref<Faction>, &mut RayCaster), With<VisionCapabilities>>,
+    relations: Res<FactionRelations>,
+) {
+    for (faction, mut ray_caster) in &mut vision_query {
+        if faction.is_changed() || relations.is_changed() {
+            ray_caster.query_filter.mask =
+                relations.hostile_hurtboxes(*faction) | GameCollisionLayer::HighObstacle;
+        }
+    }
+}
+
 /// Updates the `Vision` component's direction for each entity:
-/// - If the entity is actively targeting something, aim at it.
+/// - If the entity is actively targeting something, aim at it, leading the shot for ranged AI.
 /// - Otherwise, aim in the direction it is facing.
 fn update_aim_position(
     mut character_query: Query<
-        (&mut Vision, &TargetInfo, Has<Targeting>, &FacingDirection),
+        (
+            &mut Vision,
+            &TargetInfo,
+            Has<Targeting>,
+            &FacingDirection,
+            Option<&AimSolution>,
+        ),
         Without<Player>,
     >,
 ) {
-    character_query
-        .par_iter_mut()
-        .for_each(|(mut vision, target_info, has_target, facing_dir)| {
+    character_query.par_iter_mut().for_each(
+        |(mut vision, target_info, has_target, facing_dir, aim)| {
             vision.aim_direction = if has_target {
-                target_info.direction
+                aim.and_then(|aim| aim.direction)
+                    .unwrap_or(target_info.direction)
             } else {
                 facing_dir.to_vec2()
             };
-        });
+        },
+    );
 }
 
 /// Updates the direction and distance of the watched (or targeted) entity,
@@ -121,7 +161,7 @@ fn update_target_info(
         &mut TargetInfo,
         &mut RayCaster,
         &Transform,
-        &Watching,
+        Option<&Watching>,
         Option<&Targeting>,
     )>,
     target_query: Query<&Transform>,
@@ -129,7 +169,9 @@ fn update_target_info(
     npc_query.par_iter_mut().for_each(
         |(mut target_info, mut ray_caster, transform, watching, targeting)| {
             // Track distance and direction to target if there is one, otherwise track watching
-            let target_entity = targeting.map_or(watching.0, |t| t.0);
+            let Some(target_entity) = targeting.map(|t| t.0).or(watching.map(|w| w.0)) else {
+                return;
+            };
 
             if let Ok(target_transform) = target_query.get(target_entity) {
                 let target_direction = (target_transform.translation.xy()
@@ -158,7 +200,7 @@ fn update_target_info(
 /// - Checks if the direction to the watched entity is within the vision cone
 ///
 /// Requirements:
-/// - The NPC must have a `Watching` component referencing the target
+/// - The NPC must have a `Watching` or `Targeting` component referencing the target
 /// - The target must have a `WatchedBy` component and optionally `Children` (e.g. for colliders)
 fn is_target_in_sight(
     mut npc_query: Query<(
@@ -166,7 +208,7 @@ fn is_target_in_sight(
         &RayHits,
         &Vision,
         &VisionCapabilities,
-        &Watching,
+        Option<&Watching>,
         Option<&Targeting>,
     )>,
     target_query: Query<Option<&Children>, Or<(With<WatchedBy>, With<TargetedBy>)>>,
@@ -175,13 +217,16 @@ fn is_target_in_sight(
         |(mut target_info, ray_hits, vision, vision_capabilities, watching, targeting)| {
             target_info.line_of_sight = false;
 
+            let Some(target_entity) = targeting.map(|t| t.0).or(watching.map(|w| w.0)) else {
+                target_info.in_vision_cone = false;
+                return;
+            };
+
             // Check if target is in vision cone angle
             let vision_cone_dot = vision_capabilities.vision_cone_radius.cos();
             target_info.in_vision_cone =
                 target_info.direction.dot(vision.aim_direction) > vision_cone_dot;
 
-            let target_entity = targeting.map_or(watching.0, |t| t.0);
-
             for hit in ray_hits {
                 // Check direct match
                 if hit.entity == target_entity {
@@ -207,37 +252,108 @@ fn is_target_in_sight(
 
 /// Handles auto-targeting when an entity is attacked.
 /// Ignores line of sight or cone checks — instant rage response.
-/// For now, this will just target the entity the AI is watching, not necessarily the entity that damaged them.
-/// TODO: Refactor projectiles/melee/damage to hold the "original source" entity so this can be improved
+/// Blames whoever wielded the weapon or fired the projectile if known, otherwise the watched entity.
+/// They gain threat, and become the target if we weren't already fighting someone.
+/// Damage from a faction we aren't hostile to (ex. friendly fire) is forgiven.
 fn on_damage_aggro(
     damage_dealt: On<DamageDealt>,
     mut commands: Commands,
-    target_query: Query<&Watching>,
+    mut target_query: Query<(
+        Option<&Watching>,
+        &Faction,
+        Has<Targeting>,
+        Option<&mut ThreatTable>,
+    )>,
+    faction_query: Query<&Faction>,
+    weapon_query: Query<&ItemOf>,
+    projectile_query: Query<&FiredBy>,
+    relations: Res<FactionRelations>,
 ) {
     let damaged_entity = damage_dealt.entity;
 
-    if let Ok(watching) = target_query.get(damaged_entity) {
-        debug!(
-            "I've been hit: {}, attacking: {}",
-            damaged_entity, watching.0
-        );
-        commands
-            .entity(damaged_entity)
-            .insert((TargetLock, Targeting(watching.0)));
+    let Ok((watching, faction, has_target, threat_table)) = target_query.get_mut(damaged_entity)
+    else {
+        return;
+    };
+
+    let attacker = damage_dealt.damage_source.and_then(|source| {
+        weapon_query
+            .get(source)
+            .map(|item_of| item_of.0)
+            .or_else(|_| projectile_query.get(source).map(|fired_by| fired_by.0))
+            .ok()
+    });
+    let Some(target) = attacker.or(watching.map(|watching| watching.0)) else {
+        return;
+    };
+
+    if faction_query
+        .get(target)
+        .is_ok_and(|target_faction| relations.is_hostile(*faction, *target_faction))
+    {
+        debug!("I've been hit: {}, attacking: {}", damaged_entity, target);
+
+        if let Some(mut threat_table) = threat_table {
+            threat_table.add(target, damage_dealt.damage * DAMAGE_THREAT);
+        }
+
+        commands.entity(damaged_entity).insert(TargetLock);
+        if !has_target {
+            commands.entity(damaged_entity).insert(Targeting(target));
+        }
 
         schedule_component_removal::<TargetLock>(&mut commands, damaged_entity, 6.0);
     }
 }
 
-/// Starts targeting the watched entity if it is in sight and in the vision cone.
+/// Watches the nearest hostile character within vision range, whoever they are, so anyone can end
+/// up fighting anyone the faction relations allow. Keeps watching the last one if none are close.
+fn watch_nearest_hostile(
+    mut commands: Commands,
+    watcher_query: Query<
+        (Entity, &Transform, &Faction, &RayCaster, Option<&Watching>),
+        (With<VisionCapabilities>, Without<Targeting>),
+    >,
+    character_query: Query<(Entity, &Transform, &Faction), With<Health>>,
+    relations: Res<FactionRelations>,
+) {
+    for (entity, transform, faction, ray_caster, watching) in &watcher_query {
+        let position = transform.translation.xy();
+
+        let nearest = character_query
+            .iter()
+            .filter(|(other, _, other_faction)| {
+                *other != entity && relations.is_hostile(*faction, **other_faction)
+            })
+            .map(|(other, other_transform, _)| {
+                (other, other_transform.translation.xy().distance(position))
+            })
+            .filter(|(_, distance)| *distance <= ray_caster.max_distance)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((nearest, _)) = nearest
+            && watching.is_none_or(|watching| watching.0 != nearest)
+        {
+            commands.entity(entity).insert(Watching(nearest));
+        }
+    }
+}
+
+/// Starts targeting the watched entity if it is hostile, in sight and in the vision cone.
 fn should_target_watched(
     mut commands: Commands,
-    npc_query: Query<(&TargetInfo, &Watching, Entity), Without<Targeting>>,
+    npc_query: Query<(&TargetInfo, &Watching, &Faction, Entity), Without<Targeting>>,
+    faction_query: Query<&Faction>,
+    relations: Res<FactionRelations>,
 ) {
     npc_query
         .iter()
-        .for_each(|(target_info, watching, entity)| {
-            if target_info.line_of_sight && target_info.in_vision_cone {
+        .for_each(|(target_info, watching, faction, entity)| {
+            let is_hostile = faction_query
+                .get(watching.0)
+                .is_ok_and(|watched_faction| relations.is_hostile(*faction, *watched_faction));
+
+            if is_hostile && target_info.line_of_sight && target_info.in_vision_cone {
                 commands.entity(entity).insert(Targeting(watching.0));
             }
         });
@@ -256,3 +372,15 @@ fn should_stop_targeting(
             }
         });
 }
+
+/// Keeps track of where the target was while they are in sight
+fn remember_target_position(mut npc_query: Query<(&mut TargetInfo, &Transform), With<Targeting>>) {
+    npc_query
+        .par_iter_mut()
+        .for_each(|(mut target_info, transform)| {
+            if target_info.line_of_sight {
+                target_info.last_known_position =
+                    Some(transform.translation.xy() + target_info.direction * target_info.distance);
+            }
+        });
+}
@@ -0,0 +1,79 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::prelude::*;
+
+/// Percent reduction can never go past this, no matter how many sources stack
+const MAX_PERCENT_REDUCTION: f32 = 0.75;
+
+/// Flat reduction can't bring a hit below this fraction of its percent-reduced damage
+const MIN_DAMAGE_AFTER_FLAT: f32 = 0.2;
+
+/// Reduces incoming damage. Characters can have armor themselves, and also get it from their
+/// equipped items and their statuses (buffs).
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct Armor {
+    /// Damage subtracted from every hit
+    pub flat: f32,
+    /// Percentage (0.0 to 1.0) of every hit negated
+    pub percent: f32,
+}
+
+impl Armor {
+    pub fn new(flat: f32, percent: f32) -> Self {
+        Self { flat, percent }
+    }
+
+    /// Combines armor from several sources. Flat armor adds up, while percentages stack
+    /// multiplicatively so each extra source is worth a little less than the last.
+    pub fn stack(self, other: Armor) -> Self {
+        Self {
+            flat: self.flat + other.flat,
+            percent: 1.0 - (1.0 - self.percent) * (1.0 - other.percent.clamp(0.0, 1.0)),
+        }
+    }
+
+    pub fn percent_reduction(&self) -> f32 {
+        self.percent.clamp(0.0, MAX_PERCENT_REDUCTION)
+    }
+
+    /// Damage left after armor, percent is applied before flat so flat armor stays useful
+    /// against big hits without making small ones do nothing
+    pub fn reduce(&self, damage: f32) -> f32 {
+        let after_percent = damage * (1.0 - self.percent_reduction());
+        (after_percent - self.flat.max(0.0)).max(after_percent * MIN_DAMAGE_AFTER_FLAT)
+    }
+}
+
+/// Damage sources with this bypass armor entirely (ex. poison)
+#[derive(Component, Clone, Default)]
+pub struct IgnoresArmor;
+
+/// Total armor of a character, from themselves, their equipped items and their statuses
+#[derive(SystemParam)]
+pub struct TotalArmor<'w, 's> {
+    armor: Query<'w, 's, &'static Armor>,
+    sources: Query<'w, 's, (Option<&'static Items>, Option<&'static Statuses>)>,
+    equipped: Query<'w, 's, (), With<Equipped>>,
+}
+
+impl TotalArmor<'_, '_> {
+    pub fn get(&self, entity: Entity) -> Armor {
+        let own_armor = self.armor.get(entity).copied().unwrap_or_default();
+
+        let Ok((items, statuses)) = self.sources.get(entity) else {
+            return own_armor;
+        };
+
+        let equipment = items
+            .into_iter()
+            .flat_map(RelationshipTarget::iter)
+            .filter(|&item| self.equipped.contains(item));
+
+        let statuses = statuses.into_iter().flat_map(RelationshipTarget::iter);
+
+        equipment
+            .chain(statuses)
+            .filter_map(|source| self.armor.get(source).ok())
+            .fold(own_armor, |total, armor| total.stack(*armor))
+    }
+}
@@ -1,40 +1,23 @@
 use avian2d::prelude::*;
 
-use bevy::{ecs::entity_disabling::Disabled, prelude::*};
+use bevy::prelude::*;
 use rand::Rng;
 
 use crate::{
     combat::{
+        armor::{IgnoresArmor, TotalArmor},
+        faction::Faction,
         health::Health,
         invulnerable::IFrames,
-        status_effects::{EffectOf, Effects, StatusOf},
+        knockback::Staggered,
+        status_effects::{Effects, apply_effects},
+    },
+    prelude::{
+        ActiveShield, Block, Blocked, GameCollisionLayer, ItemOf, Mana, ManaCost, Offhand, Player,
+        StopUsingEquipment, Vision,
     },
-    prelude::{GameCollisionLayer, Player},
 };
 
-#[derive(PartialEq, Clone, Copy, Debug)]
-pub enum DamageSource {
-    Player,
-    Enemy,
-    NPC,
-    Environment,
-}
-
-impl From<DamageSource> for LayerMask {
-    fn from(source: DamageSource) -> Self {
-        match source {
-            DamageSource::Player | DamageSource::NPC => GameCollisionLayer::EnemyHurtBox.to_bits(),
-            DamageSource::Enemy => GameCollisionLayer::AllyHurtBox.to_bits(),
-            DamageSource::Environment => {
-                // Environment can affect all characters
-                GameCollisionLayer::AllyHurtBox.to_bits()
-                    | GameCollisionLayer::EnemyHurtBox.to_bits()
-            }
-        }
-        .into()
-    }
-}
-
 #[derive(Copy, Clone)]
 pub enum Damage {
     Single(f32),
@@ -48,6 +31,13 @@ impl Damage {
             Damage::Single(amount) => amount,
         }
     }
+
+    pub fn scaled(self, multiplier: f32) -> Self {
+        match self {
+            Damage::Range((min, max)) => Damage::Range((min * multiplier, max * multiplier)),
+            Damage::Single(amount) => Damage::Single(amount * multiplier),
+        }
+    }
 }
 
 impl Default for Damage {
@@ -60,12 +50,12 @@ impl Default for Damage {
 #[require(Sensor)]
 pub struct HurtBox;
 
-pub fn hurtbox(size: Vec2, membership: GameCollisionLayer) -> impl Bundle {
+pub fn hurtbox(size: Vec2, faction: Faction) -> impl Bundle {
     (
         HurtBox,
         Collider::rectangle(size.x, size.y),
         Transform::from_xyz(0.0, -8.0, 0.0),
-        CollisionLayers::new(membership, [GameCollisionLayer::HitBox]),
+        CollisionLayers::new(faction.hurtbox_layer(), [GameCollisionLayer::HitBox]),
     )
 }
 
@@ -74,6 +64,8 @@ pub struct AttemptDamage {
     pub entity: Entity,
     /// Not all damage gets blocked by invulnerable (ex: burn from status effect)
     pub ignore_invulnerable: bool,
+    /// Some damage goes straight through armor (ex: poison)
+    pub ignore_armor: bool,
     /// We treat damage as a range with RNG determining which value is dealt
     pub damage: Damage,
     /// Not all damage has a "Source" entity, like environmental damage or damage-over-time effects
@@ -87,6 +79,7 @@ impl Default for AttemptDamage {
         Self {
             entity: Entity::PLACEHOLDER,
             ignore_invulnerable: false,
+            ignore_armor: false,
             damage: Damage::Single(1.0),
             damage_source: None,
             direction: None,
@@ -116,6 +109,12 @@ pub(super) fn on_damage_event(
     hurt_box_query: Query<&ChildOf, With<HurtBox>>,
     mut damaged_query: Query<(&mut Health, Option<&mut IFrames>)>,
     source_query: Query<&Effects>,
+    holder_query: Query<(&Offhand, &Vision)>,
+    shield_query: Query<(&Block, &ActiveShield)>,
+    mut mana_query: Query<&mut Mana>,
+    weapon_query: Query<&ItemOf>,
+    total_armor: TotalArmor,
+    ignores_armor_query: Query<(), With<IgnoresArmor>>,
 ) {
     // Damage can be applied to an entities hurtbox, or to the entity directly
     let damaged_entity = if let Ok(child_of) = hurt_box_query.get(attempt_damage.entity) {
@@ -128,16 +127,76 @@ pub(super) fn on_damage_event(
 
     if let Ok((mut health, has_iframes)) = damaged_query.get_mut(damaged_entity) {
         // Entities have to "opt-in" to having iframes. Right now that is only the player
-        if let Some(mut iframes) = has_iframes {
-            if iframes.is_invulnerable && !attempt_damage.ignore_invulnerable {
-                return;
+        if let Some(iframes) = &has_iframes
+            && iframes.is_invulnerable
+            && !attempt_damage.ignore_invulnerable
+        {
+            return;
+        }
+
+        // Convert `Damage` to raw damage amount
+        let mut damage = attempt_damage.damage.to_float();
+
+        // A raised shield facing the incoming hit blocks some or all of it, as long as the holder
+        // can pay the mana for it
+        if let Some(direction) = attempt_damage.direction
+            && let Ok((offhand, vision)) = holder_query.get(damaged_entity)
+            && let Ok((block, active_shield)) = shield_query.get(offhand.get())
+            && block.covers(vision.aim_direction, direction)
+        {
+            let block_cost = ManaCost(block.mana_per_block);
+            let can_block = if let Ok(mut mana) = mana_query.get_mut(damaged_entity) {
+                let has_enough_mana = mana.has_enough_mana(&block_cost);
+                if has_enough_mana {
+                    mana.use_mana(&block_cost);
+                }
+                has_enough_mana
+            } else {
+                true
+            };
+
+            if can_block {
+                let parried = active_shield.is_parrying();
+
+                // Parrying a melee swing stuns the one swinging it
+                if parried
+                    && let Some(source_entity) = attempt_damage.damage_source
+                    && let Ok(item_of) = weapon_query.get(source_entity)
+                    && item_of.0 != damaged_entity
+                {
+                    commands.trigger(Staggered { entity: item_of.0 });
+                }
+
+                commands.trigger(Blocked {
+                    entity: damaged_entity,
+                    parried,
+                });
+
+                damage *= 1.0 - block.damage_reduction.clamp(0.0, 1.0);
+                if damage <= 0.0 {
+                    return;
+                }
+            } else {
+                // Out of mana, the guard breaks
+                commands.trigger(StopUsingEquipment {
+                    entity: offhand.get(),
+                });
             }
+        }
+
+        let ignores_armor = attempt_damage.ignore_armor
+            || attempt_damage
+                .damage_source
+                .is_some_and(|source| ignores_armor_query.contains(source));
+
+        if !ignores_armor {
+            damage = total_armor.get(damaged_entity).reduce(damage);
+        }
 
+        if let Some(mut iframes) = has_iframes {
             iframes.is_invulnerable = true;
         }
 
-        // Convert `Damage` to raw damage amount
-        let damage = attempt_damage.damage.to_float();
         health.take_damage(damage);
 
         // Because AttemptDamageEvent may not result in damage being applied (invulnerable or entity without health)
@@ -156,14 +215,7 @@ pub(super) fn on_damage_event(
         } else if let Some(source_entity) = attempt_damage.damage_source {
             // If entity is still alive and damage source exists and has effects list, we apply status effects
             if let Ok(effects) = source_query.get(source_entity) {
-                trace!("Applying effects: {:?}", effects);
-                effects.iter().for_each(|e| {
-                    commands
-                        .entity(e)
-                        .clone_and_spawn()
-                        .remove::<(Disabled, EffectOf)>()
-                        .insert(StatusOf(damaged_entity));
-                });
+                apply_effects(&mut commands, effects, damaged_entity);
             }
         }
     }
@@ -172,6 +224,11 @@ pub(super) fn on_damage_event(
 #[derive(Component)]
 pub struct DamageFlash(pub Timer);
 
+/// Color a sprite goes back to once a damage flash ends, for characters not drawn in their
+/// natural colors (ex. elites)
+#[derive(Component, Clone, Copy)]
+pub struct SpriteTint(pub Color);
+
 impl Default for DamageFlash {
     fn default() -> Self {
         Self(Timer::from_seconds(0.05, TimerMode::Once))
@@ -198,34 +255,14 @@ pub(super) fn on_damage_dealt_flash(
 }
 
 pub(super) fn tick_and_remove_damage_flash(
-    mut damage_flash_query: Query<(&mut DamageFlash, &mut Sprite)>,
+    mut damage_flash_query: Query<(&mut DamageFlash, &mut Sprite, Option<&SpriteTint>)>,
     time: Res<Time>,
 ) {
     damage_flash_query
         .par_iter_mut()
-        .for_each(|(mut flash, mut sprite)| {
+        .for_each(|(mut flash, mut sprite, tint)| {
             if flash.0.tick(time.delta()).is_finished() {
-                sprite.color = Color::WHITE;
+                sprite.color = tint.map_or(Color::WHITE, |tint| tint.0);
             }
         });
 }
-
-#[derive(Component, Clone)]
-pub struct Knockback(pub f32);
-
-pub(super) fn on_damage_dealt_knockback(
-    damage_dealt: On<DamageDealt>,
-    knockback_query: Query<&Knockback>,
-    mut forces: Query<Forces>,
-) -> Result {
-    if let Some(damage_source) = damage_dealt.damage_source
-        && let Ok(knockback) = knockback_query.get(damage_source)
-        && let Some(damage_direction) = damage_dealt.direction
-    {
-        info!("Applying knockback!");
-        forces
-            .get_mut(damage_dealt.entity)?
-            .apply_force(damage_direction * knockback.0 * 1_000_000.0);
-    }
-    Ok(())
-}
@@ -0,0 +1,113 @@
+use avian2d::prelude::*;
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::{combat::damage::HurtBox, prelude::*};
+
+/// Which side a character (or the attack it made) is on. Whether two factions can hurt and target
+/// each other is decided by `FactionRelations`.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Faction {
+    Player,
+    Enemy,
+    /// Hub NPCs, shopkeepers, guards...
+    Villager,
+    /// Monsters that mind their own business unless provoked
+    Wildlife,
+    /// Traps and other hazards, they have no hurtbox of their own
+    Environment,
+}
+
+impl Faction {
+    pub const ALL: [Faction; 5] = [
+        Faction::Player,
+        Faction::Enemy,
+        Faction::Villager,
+        Faction::Wildlife,
+        Faction::Environment,
+    ];
+
+    /// Collision layer for hurtboxes of characters in this faction
+    pub fn hurtbox_layer(self) -> LayerMask {
+        match self {
+            Faction::Player => GameCollisionLayer::PlayerHurtBox.into(),
+            Faction::Enemy => GameCollisionLayer::EnemyHurtBox.into(),
+            Faction::Villager => GameCollisionLayer::VillagerHurtBox.into(),
+            Faction::Wildlife => GameCollisionLayer::WildlifeHurtBox.into(),
+            Faction::Environment => LayerMask::NONE,
+        }
+    }
+}
+
+/// Hostility matrix between factions, always symmetric. Can be changed at runtime, new attacks
+/// and vision checks will pick up the change.
+#[derive(Resource, Clone, Debug)]
+pub struct FactionRelations {
+    hostile: HashSet<(Faction, Faction)>,
+}
+
+impl Default for FactionRelations {
+    fn default() -> Self {
+        let mut relations = Self {
+            hostile: HashSet::default(),
+        };
+
+        relations.set_hostile(Faction::Player, Faction::Enemy, true);
+        relations.set_hostile(Faction::Villager, Faction::Enemy, true);
+
+        // Environment hurts everyone
+        for faction in Faction::ALL {
+            if faction != Faction::Environment {
+                relations.set_hostile(Faction::Environment, faction, true);
+            }
+        }
+
+        relations
+    }
+}
+
+impl FactionRelations {
+    pub fn is_hostile(&self, a: Faction, b: Faction) -> bool {
+        self.hostile.contains(&(a, b))
+    }
+
+    pub fn set_hostile(&mut self, a: Faction, b: Faction, hostile: bool) {
+        if hostile {
+            self.hostile.insert((a, b));
+            self.hostile.insert((b, a));
+        } else {
+            self.hostile.remove(&(a, b));
+            self.hostile.remove(&(b, a));
+        }
+    }
+
+    /// Hurtbox layers an attack made by `faction` should be able to hit
+    pub fn hostile_hurtboxes(&self, faction: Faction) -> LayerMask {
+        Faction::ALL
+            .into_iter()
+            .filter(|other| self.is_hostile(faction, *other))
+            .fold(LayerMask::NONE, |mask, other| mask | other.hurtbox_layer())
+    }
+
+    /// Hurtbox layers of everyone `faction` is not hostile to, including itself
+    pub fn friendly_hurtboxes(&self, faction: Faction) -> LayerMask {
+        Faction::ALL
+            .into_iter()
+            .filter(|other| !self.is_hostile(faction, *other))
+            .fold(LayerMask::NONE, |mask, other| mask | other.hurtbox_layer())
+    }
+}
+
+/// Keeps hurtbox memberships in sync with the faction of the character they belong to, so a
+/// character changing sides (ex. being charmed) can be hit by their old allies
+pub(super) fn sync_hurtbox_factions(
+    faction_query: Query<(&Faction, &Children), Changed<Faction>>,
+    mut hurt_box_query: Query<&mut CollisionLayers, With<HurtBox>>,
+) {
+    for (faction, children) in &faction_query {
+        for child in children.iter() {
+            if let Ok(mut collision_layers) = hurt_box_query.get_mut(child) {
+                collision_layers.memberships = faction.hurtbox_layer();
+            }
+        }
+    }
+}
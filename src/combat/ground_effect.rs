@@ -0,0 +1,221 @@
+use avian2d::prelude::*;
+use bevy::{ecs::entity_disabling::Disabled, prelude::*};
+
+use crate::{
+    combat::{
+        damage::{AttemptDamage, Damage, HurtBox},
+        health::AttemptHeal,
+        status_effects::{Effects, apply_effects},
+    },
+    prelude::*,
+};
+
+/// An area on the ground that affects every character overlapping it each time `tick` finishes.
+/// What happens to those characters depends on which of `GroundDamage`, `GroundHeal` and
+/// `Effects` the area has.
+#[derive(Component, Clone)]
+#[require(
+    Lifespan::new(5.0),
+    Sensor,
+    RigidBody::Static,
+    Collider::circle(32.0),
+    CollidingEntities
+)]
+pub struct GroundEffect {
+    pub tick: Timer,
+}
+
+impl GroundEffect {
+    pub fn new(tick_secs: f32) -> Self {
+        Self {
+            tick: Timer::from_seconds(tick_secs, TimerMode::Repeating),
+        }
+    }
+
+    /// Damaging areas target those hostile to their faction, healing areas target everyone else
+    pub fn collision_layers(
+        faction: Faction,
+        heals: bool,
+        relations: &FactionRelations,
+    ) -> CollisionLayers {
+        let targets = if heals {
+            relations.friendly_hurtboxes(faction)
+        } else {
+            relations.hostile_hurtboxes(faction)
+        };
+
+        CollisionLayers::new(GameCollisionLayer::HitBox, targets)
+    }
+}
+
+impl Default for GroundEffect {
+    fn default() -> Self {
+        Self::new(0.5)
+    }
+}
+
+/// Damage dealt to each character in the area per tick. Statuses in the area's `Effects` are
+/// applied through the damage, same as with projectiles and melee weapons.
+#[derive(Component, Clone)]
+pub struct GroundDamage(pub Damage);
+
+/// Health restored to each character in the area per tick
+#[derive(Component, Clone)]
+pub struct GroundHeal(pub f32);
+
+/// Disabled ground effect "template" that gets cloned into the world by `SpawnGroundEffects`
+#[derive(Component, Clone)]
+#[require(Disabled)]
+#[relationship(relationship_target = GroundEffects)]
+pub struct GroundEffectOf(Entity);
+
+#[derive(Component, Clone)]
+#[relationship_target(relationship = GroundEffectOf, linked_spawn)]
+pub struct GroundEffects(Vec<Entity>);
+
+/// Spawns a copy of each of the entity's `GroundEffects` at `position`
+#[derive(EntityEvent)]
+pub struct SpawnGroundEffects {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub faction: Faction,
+}
+
+pub(super) fn on_spawn_ground_effects(
+    spawn_ground_effects: On<SpawnGroundEffects>,
+    mut commands: Commands,
+    spawner_query: Query<&GroundEffects>,
+    template_query: Query<Has<GroundHeal>, (With<GroundEffect>, With<Disabled>)>,
+    relations: Res<FactionRelations>,
+) {
+    let Ok(ground_effects) = spawner_query.get(spawn_ground_effects.entity) else {
+        return;
+    };
+
+    let position = spawn_ground_effects.position;
+
+    for template in ground_effects.iter() {
+        let Ok(heals) = template_query.get(template) else {
+            continue;
+        };
+
+        commands
+            .entity(template)
+            .clone_and_spawn_with_opt_out(|builder| {
+                builder.linked_cloning(true);
+            })
+            .remove::<(GroundEffectOf, Disabled)>()
+            .insert((
+                Position(position),
+                Transform::from_translation(position.extend(ZLayer::OnGround.z())),
+                spawn_ground_effects.faction,
+                GroundEffect::collision_layers(spawn_ground_effects.faction, heals, &relations),
+            ));
+    }
+}
+
+pub(super) fn tick_ground_effects(
+    mut commands: Commands,
+    mut ground_effect_query: Query<(
+        Entity,
+        &mut GroundEffect,
+        &CollidingEntities,
+        Option<&GroundDamage>,
+        Option<&GroundHeal>,
+        Option<&Effects>,
+    )>,
+    hurt_box_query: Query<&ChildOf, With<HurtBox>>,
+    time: Res<Time>,
+) {
+    for (area, mut ground_effect, colliding_entities, damage, heal, effects) in
+        &mut ground_effect_query
+    {
+        if !ground_effect.tick.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        for &hurt_box in colliding_entities.iter() {
+            let Ok(child_of) = hurt_box_query.get(hurt_box) else {
+                continue;
+            };
+
+            if let Some(damage) = damage {
+                commands.trigger(AttemptDamage {
+                    entity: hurt_box,
+                    ignore_invulnerable: true,
+                    damage: damage.0,
+                    damage_source: Some(area),
+                    ..default()
+                });
+            } else if let Some(effects) = effects {
+                apply_effects(&mut commands, effects, child_of.parent());
+            }
+
+            if let Some(heal) = heal {
+                commands.trigger(AttemptHeal {
+                    entity: child_of.parent(),
+                    amount: heal.0,
+                    healer: None,
+                });
+            }
+        }
+    }
+}
+
+pub fn fire_pool(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
+    (
+        Name::new("Fire Pool"),
+        GroundEffect::new(0.5),
+        GroundDamage(Damage::Range((1.0, 3.0))),
+        Collider::circle(32.0),
+        Lifespan::new(4.0),
+        Sprite {
+            image: sprites.flame.clone(),
+            texture_atlas: Some(TextureAtlas {
+                layout: sprite_layouts.flame_vfx.clone(),
+                ..default()
+            }),
+            color: Color::srgba(1.0, 1.0, 1.0, 0.7),
+            ..default()
+        },
+        AnimationIndices::Cycle((0..=7).cycle()),
+        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+        related!(Effects[(Burning, Lifespan::new(1.0))]),
+    )
+}
+
+pub fn frost_field(sprites: &SpriteAssets) -> impl Bundle {
+    (
+        Name::new("Frost Field"),
+        GroundEffect::new(0.5),
+        Collider::circle(48.0),
+        Lifespan::new(6.0),
+        Sprite {
+            image: sprites.grounded_ice.clone(),
+            color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+            ..default()
+        },
+        related!(Effects[(Slowed { percent: 0.4 }, Lifespan::new(0.5))]),
+    )
+}
+
+pub fn healing_circle(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
+    (
+        Name::new("Healing Circle"),
+        GroundEffect::new(1.0),
+        GroundHeal(5.0),
+        Collider::circle(48.0),
+        Lifespan::new(8.0),
+        Sprite {
+            image: sprites.tome_of_healing_effect.clone(),
+            texture_atlas: Some(TextureAtlas {
+                layout: sprite_layouts.spell_effect.clone(),
+                ..default()
+            }),
+            color: Color::srgba(1.0, 1.0, 1.0, 0.6),
+            ..default()
+        },
+        AnimationIndices::Cycle((0..=9).cycle()),
+        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+    )
+}
@@ -4,12 +4,15 @@ use bevy::prelude::*;
 pub struct AttemptHeal {
     pub entity: Entity,
     pub amount: f32,
+    /// Whoever did the healing, if anyone
+    pub healer: Option<Entity>,
 }
 
 #[derive(EntityEvent)]
 pub struct Healed {
     pub entity: Entity,
     pub amount: f32,
+    pub healer: Option<Entity>,
 }
 
 #[derive(Component)]
@@ -56,6 +59,7 @@ pub(super) fn on_healing_event(
         commands.trigger(Healed {
             entity: attempt_heal.entity,
             amount: actual_amount,
+            healer: attempt_heal.healer,
         });
         info!(
             "Entity {} healed by {:.2} points",
@@ -2,6 +2,9 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 
+/// How long an entity is invulnerable for after being hit
+const IFRAME_DURATION: Duration = Duration::from_millis(800);
+
 /// Component to mark whether an entity has iframes when hit
 #[derive(Component)]
 pub struct IFrames {
@@ -15,16 +18,35 @@ impl Default for IFrames {
     fn default() -> Self {
         Self {
             is_invulnerable: false,
-            invulnerable_timer: Timer::new(Duration::from_millis(800), TimerMode::Once),
+            invulnerable_timer: Timer::new(IFRAME_DURATION, TimerMode::Once),
             flash_timer: Some(Timer::new(Duration::from_millis(100), TimerMode::Repeating)),
         }
     }
 }
 
 impl IFrames {
+    /// Makes the entity invulnerable for `duration` (ex. while dashing), capped at the normal
+    /// iframe duration. Never shortens invulnerability that is already active.
+    pub fn grant(&mut self, duration: Duration) {
+        let elapsed = self.invulnerable_timer.duration().saturating_sub(duration);
+
+        if !self.is_invulnerable || self.invulnerable_timer.elapsed() > elapsed {
+            self.invulnerable_timer.set_elapsed(elapsed);
+        }
+
+        self.is_invulnerable = true;
+    }
+
+    /// Makes the entity invulnerable for exactly `duration`, which may be longer than the normal
+    /// iframe duration (ex. after being revived)
+    pub fn grant_for(&mut self, duration: Duration) {
+        self.invulnerable_timer = Timer::new(duration, TimerMode::Once);
+        self.is_invulnerable = true;
+    }
+
     fn reset(&mut self) {
         self.is_invulnerable = false;
-        self.invulnerable_timer.reset();
+        self.invulnerable_timer = Timer::new(IFRAME_DURATION, TimerMode::Once);
 
         if let Some(flash) = &mut self.flash_timer {
             flash.reset();
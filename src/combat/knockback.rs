@@ -0,0 +1,143 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Converts `Knockback` strength into an impulse, a character with 50 mass hit by a knockback of
+/// 10 gets launched at 400 pixels per second
+const KNOCKBACK_IMPULSE_SCALE: f32 = 2000.0;
+
+/// How long a character loses control of their movement after being knocked back
+const HIT_STUN_SECS: f32 = 0.2;
+
+/// How long a character is stunned for when their poise breaks
+const STAGGER_SECS: f32 = 0.8;
+
+/// Strength of the knockback applied to anything damaged by this entity
+#[derive(Component, Clone)]
+pub struct Knockback(pub f32);
+
+/// Percentage (0.0 to 1.0) of knockback ignored, heavy characters barely budge
+#[derive(Component, Clone)]
+pub struct KnockbackResistance(pub f32);
+
+/// Absorbs damage from small hits so they don't knock the character around. Once it breaks, the
+/// character is knocked back and staggered, and poise is restored.
+#[derive(Component, Clone)]
+pub struct Poise {
+    pub max: f32,
+    current: f32,
+    /// Poise restored per second
+    pub regeneration: f32,
+}
+
+impl Poise {
+    pub fn new(max: f32, regeneration: f32) -> Self {
+        Self {
+            max,
+            current: max,
+            regeneration,
+        }
+    }
+
+    /// Returns true if the damage broke our poise
+    fn absorb(&mut self, damage: f32) -> bool {
+        self.current -= damage;
+
+        if self.current <= 0.0 {
+            self.current = self.max;
+            return true;
+        }
+
+        false
+    }
+}
+
+impl Default for Poise {
+    fn default() -> Self {
+        Self::new(10.0, 5.0)
+    }
+}
+
+/// While present, `SimpleMotion` no longer drives the character's velocity
+#[derive(Component)]
+pub struct HitStun(Timer);
+
+impl HitStun {
+    pub fn new(duration_secs: f32) -> Self {
+        Self(Timer::from_seconds(duration_secs, TimerMode::Once))
+    }
+}
+
+/// Triggered when a character's poise breaks, anything they were in the middle of doing should be
+/// interrupted
+#[derive(EntityEvent)]
+pub struct Staggered {
+    pub entity: Entity,
+}
+
+pub(super) fn on_damage_dealt_knockback(
+    damage_dealt: On<DamageDealt>,
+    mut commands: Commands,
+    knockback_query: Query<&Knockback>,
+    mut target_query: Query<(Forces, Option<&mut Poise>, Option<&KnockbackResistance>)>,
+) {
+    // Only direct hits can knock a character around, not damage-over-time or ground effects
+    let Some(damage_direction) = damage_dealt.direction else {
+        return;
+    };
+
+    let Ok((mut forces, poise, resistance)) = target_query.get_mut(damage_dealt.entity) else {
+        return;
+    };
+
+    // Small hits get shrugged off by characters with poise
+    if let Some(mut poise) = poise {
+        if !poise.absorb(damage_dealt.damage) {
+            return;
+        }
+
+        commands.trigger(Staggered {
+            entity: damage_dealt.entity,
+        });
+    }
+
+    if let Some(damage_source) = damage_dealt.damage_source
+        && let Ok(knockback) = knockback_query.get(damage_source)
+    {
+        let knockback_taken = 1.0 - resistance.map_or(0.0, |r| r.0.clamp(0.0, 1.0));
+
+        forces.apply_linear_impulse(
+            damage_direction * knockback.0 * KNOCKBACK_IMPULSE_SCALE * knockback_taken,
+        );
+        commands
+            .entity(damage_dealt.entity)
+            .insert(HitStun::new(HIT_STUN_SECS * knockback_taken));
+    }
+}
+
+pub(super) fn on_staggered(staggered: On<Staggered>, mut commands: Commands) {
+    commands.spawn((
+        Stunned,
+        Lifespan::new(STAGGER_SECS),
+        StatusOf(staggered.entity),
+    ));
+}
+
+pub(super) fn regenerate_poise(mut poise_query: Query<&mut Poise>, time: Res<Time>) {
+    for mut poise in &mut poise_query {
+        poise.current = (poise.current + poise.regeneration * time.delta_secs()).min(poise.max);
+    }
+}
+
+pub(super) fn tick_hit_stun(
+    mut commands: Commands,
+    mut hit_stun_query: Query<(Entity, &mut HitStun)>,
+    time: Res<Time>,
+) {
+    for (entity, mut hit_stun) in &mut hit_stun_query {
+        if hit_stun.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<HitStun>();
+        }
+    }
+}
@@ -1,18 +1,26 @@
+mod armor;
 mod damage;
+mod faction;
+mod ground_effect;
 mod health;
 mod invulnerable;
+mod knockback;
 mod mana;
 mod projectile;
 mod status_effects;
 
 use bevy::prelude::*;
 
-use crate::prelude::InGameSystems;
+use crate::prelude::{CleanupZone, FactionRelations, InGameSystems, despawn_all};
 
 pub mod prelude {
+    pub use super::armor::*;
     pub use super::damage::*;
+    pub use super::faction::*;
+    pub use super::ground_effect::*;
     pub use super::health::*;
     pub use super::invulnerable::*;
+    pub use super::knockback::*;
     pub use super::mana::*;
     pub use super::projectile::*;
     pub use super::status_effects::prelude::*;
@@ -21,17 +29,26 @@ pub mod prelude {
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((projectile::plugin, status_effects::plugin));
 
+    app.init_resource::<FactionRelations>();
+
     app.add_systems(
         Update,
         ((
             invulnerable::handle_invulnerability,
             mana::regenerate_mana,
             damage::tick_and_remove_damage_flash,
+            ground_effect::tick_ground_effects,
+            faction::sync_hurtbox_factions,
+            knockback::regenerate_poise,
+            knockback::tick_hit_stun,
         )
             .in_set(InGameSystems::Simulation),),
     )
     .add_observer(health::on_healing_event)
     .add_observer(damage::on_damage_event)
     .add_observer(damage::on_damage_dealt_flash)
-    .add_observer(damage::on_damage_dealt_knockback);
+    .add_observer(knockback::on_damage_dealt_knockback)
+    .add_observer(knockback::on_staggered)
+    .add_observer(ground_effect::on_spawn_ground_effects)
+    .add_observer(despawn_all::<CleanupZone, ground_effect::GroundEffect>);
 }
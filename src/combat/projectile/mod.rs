@@ -0,0 +1,282 @@
+mod modifiers;
+
+pub use modifiers::*;
+
+use avian2d::prelude::*;
+use bevy::{ecs::entity_disabling::Disabled, platform::collections::HashSet, prelude::*};
+
+use crate::{
+    combat::{
+        damage::{AttemptDamage, Damage, HurtBox},
+        knockback::Knockback,
+    },
+    prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            modifiers::steer_homing_projectiles.in_set(InGameSystems::Simulation),
+            handle_collisions.in_set(InGameSystems::Collision),
+        ),
+    );
+
+    app.add_observer(modifiers::on_impact_split)
+        .add_observer(modifiers::on_impact_explode)
+        .add_observer(modifiers::on_impact_ground_effects);
+
+    app.add_observer(despawn_all::<CleanupZone, Projectile>);
+}
+
+#[derive(Component, Clone)]
+#[require(
+    Lifespan::new(1.0),
+    Sensor,
+    RigidBody::Kinematic,
+    Collider::rectangle(10.0, 10.0),
+    CollidingEntities,
+    ProjectileHits,
+    AnimationIndices::Cycle((0..=4).cycle()),
+    AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
+    Disabled
+)]
+pub struct Projectile {
+    pub damage: Damage,
+    pub speed: f32,
+    pub forward_offset: f32,
+    pub angle_offset: f32,
+}
+
+impl Default for Projectile {
+    fn default() -> Self {
+        Self {
+            damage: Damage::Range((5.0, 10.0)),
+            speed: 600.0,
+            forward_offset: 25.0,
+            angle_offset: 0.0,
+        }
+    }
+}
+
+impl Projectile {
+    /// Projectiles hit hurtboxes of factions hostile to whoever fired them, are stopped by walls
+    /// and can be reflected
+    pub fn collision_layers(faction: Faction, relations: &FactionRelations) -> CollisionLayers {
+        CollisionLayers::new(
+            GameCollisionLayer::PROJECTILE_MEMBERSHIPS,
+            relations.hostile_hurtboxes(faction)
+                | GameCollisionLayer::HighObstacle
+                | GameCollisionLayer::ProjectileReflector,
+        )
+    }
+}
+
+/// Character that fired (or last reflected) the projectile
+#[derive(Component, Clone)]
+pub struct FiredBy(pub Entity);
+
+/// Weapon the projectile was fired from
+#[derive(Component, Clone)]
+pub struct FiredFrom(pub Entity);
+
+/// Entities a projectile has already collided with, so piercing and bouncing projectiles only
+/// interact with each entity once
+#[derive(Component, Clone, Default, Deref, DerefMut)]
+pub struct ProjectileHits(HashSet<Entity>);
+
+#[derive(Component, Clone)]
+#[relationship(relationship_target = Projectiles)]
+pub struct ProjectileOf(Entity);
+
+#[derive(Component, Clone)]
+#[relationship_target(relationship = ProjectileOf, linked_spawn)]
+pub struct Projectiles(Vec<Entity>);
+
+pub fn fireball(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    angle_offset: f32,
+) -> impl Bundle {
+    (
+        Projectile {
+            damage: Damage::Single(3.0),
+            speed: 600.0,
+            forward_offset: 25.0,
+            angle_offset,
+        },
+        Sprite::from_atlas_image(
+            sprites.fire_ball.clone(),
+            TextureAtlas {
+                layout: sprite_layouts.fireball_layout.clone(),
+                index: 0,
+            },
+        ),
+        Knockback(5.0),
+        related!(Effects[(Burning, Lifespan::new(2.5))]),
+    )
+}
+
+pub fn icebolt(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    angle_offset: f32,
+) -> impl Bundle {
+    (
+        Projectile {
+            damage: Damage::Range((10.0, 20.0)),
+            speed: 500.0,
+            forward_offset: 25.0,
+            angle_offset,
+        },
+        Sprite::from_atlas_image(
+            sprites.ice_bolt.clone(),
+            TextureAtlas {
+                layout: sprite_layouts.ice_bolt_layout.clone(),
+                index: 0,
+            },
+        ),
+        Knockback(5.0),
+        related!(Effects[(Frozen, Lifespan::new(0.7))]),
+    )
+}
+
+pub fn arcane_bolt(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    angle_offset: f32,
+) -> impl Bundle {
+    (
+        Projectile {
+            damage: Damage::Range((4.0, 8.0)),
+            speed: 350.0,
+            forward_offset: 25.0,
+            angle_offset,
+        },
+        Sprite {
+            color: Color::srgb(0.7, 0.3, 1.0),
+            ..Sprite::from_atlas_image(
+                sprites.ice_bolt.clone(),
+                TextureAtlas {
+                    layout: sprite_layouts.ice_bolt_layout.clone(),
+                    index: 0,
+                },
+            )
+        },
+        Lifespan::new(2.0),
+        Homing::default(),
+        Pierce(2),
+    )
+}
+
+pub fn meteor(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    angle_offset: f32,
+) -> impl Bundle {
+    (
+        Projectile {
+            damage: Damage::Range((8.0, 12.0)),
+            speed: 400.0,
+            forward_offset: 25.0,
+            angle_offset,
+        },
+        Sprite::from_atlas_image(
+            sprites.fire_ball.clone(),
+            TextureAtlas {
+                layout: sprite_layouts.fireball_layout.clone(),
+                index: 0,
+            },
+        ),
+        Knockback(10.0),
+        Ricochet(1),
+        Explosive {
+            radius: 48.0,
+            damage: Damage::Range((4.0, 8.0)),
+        },
+        SplitOnImpact {
+            count: 3,
+            spread: std::f32::consts::FRAC_PI_2,
+            lifespan: 0.4,
+        },
+        related!(Effects[(Burning, Lifespan::new(2.5))]),
+        related!(GroundEffects[fire_pool(sprites, sprite_layouts)]),
+    )
+}
+
+fn handle_collisions(
+    mut commands: Commands,
+    mut projectile_query: Query<(
+        Entity,
+        &Projectile,
+        &CollidingEntities,
+        &mut ProjectileHits,
+        &mut LinearVelocity,
+        &mut Transform,
+        Option<&mut Pierce>,
+        Option<&mut Ricochet>,
+    )>,
+    hurt_box_query: Query<&HurtBox>,
+    reflector_query: Query<&ProjectileReflection>,
+    spatial_query: SpatialQuery,
+) {
+    for (
+        projectile_entity,
+        projectile,
+        colliding_entities,
+        mut hits,
+        mut velocity,
+        mut transform,
+        mut pierce,
+        mut ricochet,
+    ) in &mut projectile_query
+    {
+        // Forget walls we are no longer touching so a projectile can bounce off the same wall twice
+        hits.retain(|entity| {
+            hurt_box_query.contains(*entity) || colliding_entities.contains(entity)
+        });
+
+        for &colliding_entity in colliding_entities.iter() {
+            if !hits.insert(colliding_entity) || reflector_query.contains(colliding_entity) {
+                continue;
+            }
+
+            // If the thing we collide with has a HurtBox, lets try to damage it!
+            if hurt_box_query.contains(colliding_entity) {
+                commands.trigger(AttemptDamage {
+                    entity: colliding_entity,
+                    damage: projectile.damage,
+                    damage_source: Some(projectile_entity),
+                    direction: Some(velocity.normalize()),
+                    ..default()
+                });
+
+                if let Some(pierce) = pierce.as_mut()
+                    && pierce.0 > 0
+                {
+                    pierce.0 -= 1;
+                    continue;
+                }
+            } else if let Some(ricochet) = ricochet.as_mut()
+                && ricochet.0 > 0
+                && let Some(normal) = modifiers::obstacle_normal(
+                    &spatial_query,
+                    transform.translation.xy(),
+                    velocity.0,
+                )
+            {
+                ricochet.0 -= 1;
+                velocity.0 = velocity.0.reflect(normal);
+                transform.rotation = Quat::from_rotation_z(velocity.0.to_angle());
+                continue;
+            }
+
+            commands.trigger(ProjectileImpact {
+                entity: projectile_entity,
+                position: transform.translation.xy(),
+            });
+            commands.entity(projectile_entity).despawn();
+            break;
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use avian2d::prelude::*;
+use bevy::{ecs::entity_disabling::Disabled, prelude::*};
+
+use crate::{
+    combat::{
+        damage::{AttemptDamage, Damage, HurtBox},
+        ground_effect::{GroundEffects, SpawnGroundEffects},
+        projectile::{Projectile, ProjectileHits},
+    },
+    prelude::*,
+};
+
+/// How far behind a projectile we start the ray used to find the surface it bounced off of
+const RICOCHET_RAY_OFFSET: f32 = 16.0;
+/// Explosions can be heard from well outside their blast
+const EXPLOSION_NOISE_RADIUS: f32 = 400.0;
+
+/// Projectile passes through this many hurtboxes before being despawned
+#[derive(Component, Clone)]
+pub struct Pierce(pub u32);
+
+/// Projectile bounces off this many `HighObstacle` walls before being despawned
+#[derive(Component, Clone)]
+pub struct Ricochet(pub u32);
+
+/// Projectile steers towards the nearest hurtbox it is able to damage
+#[derive(Component, Clone)]
+pub struct Homing {
+    /// Max rotation of the projectile's velocity, in **radians** per second
+    pub turn_rate: f32,
+    /// Max distance to a hurtbox for it to be homed in on
+    pub range: f32,
+}
+
+impl Default for Homing {
+    fn default() -> Self {
+        Self {
+            turn_rate: 4.0,
+            range: 250.0,
+        }
+    }
+}
+
+/// Projectile splits into `count` copies of itself on impact, spread evenly across `spread`
+/// radians around its direction of travel. Copies keep all other modifiers.
+#[derive(Component, Clone)]
+pub struct SplitOnImpact {
+    pub count: u32,
+    pub spread: f32,
+    /// Lifespan of the projectiles created by the split
+    pub lifespan: f32,
+}
+
+/// Projectile damages every hurtbox it can hit within `radius` on impact
+#[derive(Component, Clone)]
+pub struct Explosive {
+    pub radius: f32,
+    pub damage: Damage,
+}
+
+/// Triggered right before a projectile is despawned due to a collision
+#[derive(EntityEvent)]
+pub struct ProjectileImpact {
+    pub entity: Entity,
+    pub position: Vec2,
+}
+
+/// Casts a ray along the projectile's path to find the normal of the wall it ran into
+pub(super) fn obstacle_normal(
+    spatial_query: &SpatialQuery,
+    position: Vec2,
+    velocity: Vec2,
+) -> Option<Vec2> {
+    let direction = Dir2::new(velocity).ok()?;
+
+    // The projectile is already overlapping the wall, so start the ray a bit behind it
+    spatial_query
+        .cast_ray(
+            position - direction * RICOCHET_RAY_OFFSET,
+            direction,
+            RICOCHET_RAY_OFFSET * 2.0,
+            true,
+            &SpatialQueryFilter::from_mask(GameCollisionLayer::HighObstacle),
+        )
+        .map(|hit| hit.normal)
+}
+
+pub(super) fn steer_homing_projectiles(
+    time: Res<Time>,
+    mut projectile_query: Query<(
+        &Homing,
+        &ProjectileHits,
+        &CollisionLayers,
+        &mut LinearVelocity,
+        &mut Transform,
+    )>,
+    hurt_box_query: Query<(Entity, &GlobalTransform, &CollisionLayers), With<HurtBox>>,
+) {
+    for (homing, hits, collision_layers, mut velocity, mut transform) in &mut projectile_query {
+        let position = transform.translation.xy();
+
+        // Only home in on hurtboxes this projectile can damage and hasn't already hit
+        let closest_target = hurt_box_query
+            .iter()
+            .filter(|(hurt_box, _, hurt_box_layers)| {
+                !hits.contains(hurt_box)
+                    && (collision_layers.filters & hurt_box_layers.memberships) != LayerMask::NONE
+            })
+            .map(|(_, hurt_box_transform, _)| hurt_box_transform.translation().xy())
+            .filter(|target| target.distance(position) <= homing.range)
+            .min_by(|a, b| {
+                a.distance_squared(position)
+                    .total_cmp(&b.distance_squared(position))
+            });
+
+        let Some(target) = closest_target else {
+            continue;
+        };
+
+        let max_turn = homing.turn_rate * time.delta_secs();
+        let turn = velocity
+            .0
+            .angle_to(target - position)
+            .clamp(-max_turn, max_turn);
+
+        velocity.0 = Vec2::from_angle(turn).rotate(velocity.0);
+        transform.rotation = Quat::from_rotation_z(velocity.0.to_angle());
+    }
+}
+
+pub(super) fn on_impact_split(
+    impact: On<ProjectileImpact>,
+    mut commands: Commands,
+    projectile_query: Query<(&SplitOnImpact, &Projectile, &LinearVelocity)>,
+) {
+    let Ok((split, projectile, velocity)) = projectile_query.get(impact.entity) else {
+        return;
+    };
+
+    let direction = velocity.normalize_or_zero();
+    let (start_angle, angle_step) = if split.count > 1 {
+        (-split.spread / 2.0, split.spread / (split.count - 1) as f32)
+    } else {
+        (0.0, 0.0)
+    };
+
+    for i in 0..split.count {
+        let split_direction =
+            Vec2::from_angle(start_angle + angle_step * i as f32).rotate(direction);
+
+        commands
+            .entity(impact.entity)
+            .clone_and_spawn_with_opt_out(|builder| {
+                // Only the original projectile leaves ground effects behind
+                builder.deny::<(SplitOnImpact, GroundEffects)>();
+                builder.linked_cloning(true);
+            })
+            .remove::<Disabled>()
+            .insert((
+                Position(impact.position),
+                Rotation::radians(split_direction.to_angle()),
+                Transform {
+                    translation: impact.position.extend(ZLayer::InAir.z()),
+                    rotation: Quat::from_rotation_z(split_direction.to_angle()),
+                    ..default()
+                },
+                LinearVelocity(split_direction * projectile.speed),
+                Lifespan::new(split.lifespan),
+            ));
+    }
+}
+
+pub(super) fn on_impact_explode(
+    impact: On<ProjectileImpact>,
+    mut commands: Commands,
+    projectile_query: Query<(&Explosive, &CollisionLayers, Option<&Faction>)>,
+    hurt_box_query: Query<&GlobalTransform, With<HurtBox>>,
+    spatial_query: SpatialQuery,
+    sprites: Res<SpriteAssets>,
+    sprite_layouts: Res<SpriteSheetLayouts>,
+) {
+    let Ok((explosive, collision_layers, faction)) = projectile_query.get(impact.entity) else {
+        return;
+    };
+
+    // Explosions can hit anything the projectile itself could have hit
+    let caught_in_explosion = spatial_query.shape_intersections(
+        &Collider::circle(explosive.radius),
+        impact.position,
+        0.0,
+        &SpatialQueryFilter::from_mask(collision_layers.filters),
+    );
+
+    for entity in caught_in_explosion {
+        if let Ok(hurt_box_transform) = hurt_box_query.get(entity) {
+            commands.trigger(AttemptDamage {
+                entity,
+                damage: explosive.damage,
+                damage_source: Some(impact.entity),
+                direction: Some(
+                    (hurt_box_transform.translation().xy() - impact.position).normalize_or_zero(),
+                ),
+                ..default()
+            });
+        }
+    }
+
+    commands.spawn(explosion_vfx(
+        &sprites,
+        &sprite_layouts,
+        impact.position,
+        explosive.radius,
+    ));
+
+    commands.trigger(Noise {
+        position: impact.position,
+        radius: EXPLOSION_NOISE_RADIUS,
+        faction: faction.copied(),
+    });
+}
+
+pub(super) fn on_impact_ground_effects(
+    impact: On<ProjectileImpact>,
+    mut commands: Commands,
+    projectile_query: Query<&Faction, With<GroundEffects>>,
+) {
+    let Ok(faction) = projectile_query.get(impact.entity) else {
+        return;
+    };
+
+    commands.trigger(SpawnGroundEffects {
+        entity: impact.entity,
+        position: impact.position,
+        faction: *faction,
+    });
+}
+
+fn explosion_vfx(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    position: Vec2,
+    radius: f32,
+) -> impl Bundle {
+    (
+        Sprite::from_atlas_image(
+            sprites.flame.clone(),
+            TextureAtlas {
+                layout: sprite_layouts.flame_vfx.clone(),
+                ..default()
+            },
+        ),
+        Transform {
+            translation: position.extend(ZLayer::InAir.z()),
+            // flame sprite is 32 pixels wide, scale it to cover the explosion
+            scale: Vec3::new(radius / 16.0, radius / 32.0, 1.0),
+            ..default()
+        },
+        AnimationIndices::OneShot(0..=7),
+        AnimationTimer(Timer::from_seconds(0.05, TimerMode::Repeating)),
+        Lifespan::new(0.4),
+    )
+}
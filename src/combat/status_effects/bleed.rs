@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+
+use crate::{
+    combat::status_effects::{StatusApplied, StatusOf, damage_over_time::DamageOverTime},
+    prelude::*,
+};
+
+/// Bleeding deals more damage while the afflicted character is moving
+#[derive(Component, Clone)]
+#[require(DamageOverTime::new(1.0, 0.5))]
+pub struct Bleeding {
+    /// Damage multiplier applied while the bleeding character is moving
+    pub moving_multiplier: f32,
+}
+
+impl Default for Bleeding {
+    fn default() -> Self {
+        Bleeding {
+            moving_multiplier: 3.0,
+        }
+    }
+}
+
+pub(super) fn apply_bleeding(
+    mut commands: Commands,
+    status_query: Query<(Entity, &StatusOf, &Lifespan), (With<Bleeding>, Without<StatusApplied>)>,
+    sprites: Res<SpriteAssets>,
+    sprite_layouts: Res<SpriteSheetLayouts>,
+) {
+    status_query
+        .iter()
+        .for_each(|(status, status_of, lifespan)| {
+            commands.entity(status).insert(StatusApplied);
+
+            commands.entity(status_of.0).with_child(bleed_vfx(
+                &sprites,
+                &sprite_layouts,
+                lifespan.0.clone(),
+            ));
+        });
+}
+
+pub(super) fn update_bleeding(
+    mut status_query: Query<(&Bleeding, &StatusOf, &mut DamageOverTime)>,
+    motion_query: Query<&SimpleMotion>,
+) {
+    for (bleeding, status_of, mut dot) in &mut status_query {
+        let is_moving = motion_query
+            .get(status_of.0)
+            .is_ok_and(SimpleMotion::is_moving);
+
+        dot.multiplier = if is_moving {
+            bleeding.moving_multiplier
+        } else {
+            1.0
+        };
+    }
+}
+
+fn bleed_vfx(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    duration: Timer,
+) -> impl Bundle {
+    (
+        Sprite {
+            color: Color::srgba(0.6, 0.0, 0.0, 0.8),
+            ..Sprite::from_atlas_image(
+                sprites.flame.clone(),
+                TextureAtlas {
+                    layout: sprite_layouts.flame_vfx.clone(),
+                    ..default()
+                },
+            )
+        },
+        Transform {
+            translation: Vec3::new(
+                0.0,
+                CHARACTER_FEET_POS_OFFSET + 4.0,
+                ZLayer::SpriteForeground.z(),
+            ),
+            scale: Vec3::new(0.6, 0.4, 1.0),
+            ..default()
+        },
+        AnimationIndices::Cycle((0..=7).cycle()),
+        AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
+        Lifespan(duration),
+    )
+}
@@ -1,53 +1,13 @@
 use bevy::prelude::*;
 
 use crate::{
-    combat::{
-        damage::{AttemptDamage, Damage},
-        status_effects::{StatusApplied, StatusOf},
-    },
+    combat::status_effects::{StatusApplied, StatusOf, damage_over_time::DamageOverTime},
     prelude::*,
 };
 
-#[derive(Component, Clone)]
-pub struct Burning {
-    pub damage: f32,
-    pub damage_frequency: Timer,
-}
-
-impl Default for Burning {
-    fn default() -> Burning {
-        Burning {
-            damage: 2.0,
-            damage_frequency: Timer::from_seconds(0.5, TimerMode::Repeating),
-        }
-    }
-}
-
-pub(super) fn tick_burn(mut burn_query: Query<&mut Burning>, time: Res<Time>) {
-    for mut burn_status in &mut burn_query {
-        burn_status.damage_frequency.tick(time.delta());
-    }
-}
-
-// TODO: Modify this to be a "DamagePerSecond" component + system since it isn't specific to burning
-pub(super) fn while_burning(
-    mut commands: Commands,
-    status_query: Query<(&Burning, &StatusOf)>,
-    mut health_query: Query<Entity, With<Health>>,
-) {
-    for (burn, status_of) in status_query.iter() {
-        if let Ok(entity) = health_query.get_mut(status_of.0)
-            && burn.damage_frequency.just_finished()
-        {
-            commands.trigger(AttemptDamage {
-                entity,
-                ignore_invulnerable: true,
-                damage: Damage::Single(burn.damage),
-                ..default()
-            });
-        }
-    }
-}
+#[derive(Component, Clone, Default)]
+#[require(DamageOverTime::new(2.0, 0.5))]
+pub struct Burning;
 
 pub(super) fn apply_burning(
     mut commands: Commands,
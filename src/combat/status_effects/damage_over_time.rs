@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::{
+    combat::{
+        armor::IgnoresArmor,
+        damage::{AttemptDamage, Damage},
+        status_effects::StatusOf,
+    },
+    prelude::*,
+};
+
+/// Generic "damage per tick" status. Specific statuses like `Burning` or `Poisoned` require this
+/// component and only add their own visuals or rules for scaling the damage.
+#[derive(Component, Clone)]
+pub struct DamageOverTime {
+    /// Damage dealt each time `damage_frequency` finishes
+    pub damage: f32,
+    pub damage_frequency: Timer,
+    /// Scales `damage`, updated by the status requiring this component (ex. poison stacks)
+    pub multiplier: f32,
+}
+
+impl DamageOverTime {
+    pub fn new(damage: f32, frequency_secs: f32) -> Self {
+        Self {
+            damage,
+            damage_frequency: Timer::from_seconds(frequency_secs, TimerMode::Repeating),
+            multiplier: 1.0,
+        }
+    }
+}
+
+impl Default for DamageOverTime {
+    fn default() -> Self {
+        Self::new(1.0, 1.0)
+    }
+}
+
+pub(super) fn tick_damage_over_time(mut dot_query: Query<&mut DamageOverTime>, time: Res<Time>) {
+    for mut dot in &mut dot_query {
+        dot.damage_frequency.tick(time.delta());
+    }
+}
+
+pub(super) fn while_damaged_over_time(
+    mut commands: Commands,
+    status_query: Query<(&DamageOverTime, &StatusOf, Has<IgnoresArmor>)>,
+    health_query: Query<(), With<Health>>,
+) {
+    for (dot, status_of, ignore_armor) in status_query.iter() {
+        if dot.damage_frequency.just_finished() && health_query.contains(status_of.0) {
+            commands.trigger(AttemptDamage {
+                entity: status_of.0,
+                ignore_invulnerable: true,
+                ignore_armor,
+                damage: Damage::Single(dot.damage * dot.multiplier),
+                ..default()
+            });
+        }
+    }
+}
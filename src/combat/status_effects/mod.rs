@@ -1,11 +1,20 @@
+mod bleed;
 mod burn;
+mod damage_over_time;
 mod freeze;
+mod poison;
 mod slow;
+mod stun;
 
 pub mod prelude {
+    pub use super::bleed::*;
     pub use super::burn::*;
+    pub use super::damage_over_time::*;
     pub use super::freeze::*;
-    pub use super::{EffectOf, Effects, StatusOf};
+    pub use super::poison::*;
+    pub use super::slow::*;
+    pub use super::stun::*;
+    pub use super::{EffectOf, Effects, StatusOf, Statuses};
 }
 
 use bevy::{ecs::entity_disabling::Disabled, prelude::*};
@@ -17,9 +26,17 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         (
             burn::apply_burning,
-            (burn::tick_burn, burn::while_burning).chain(),
+            bleed::apply_bleeding,
             freeze::apply_frozen,
+            stun::apply_stunned,
             slow::apply_slowed,
+            (
+                poison::apply_poisoned,
+                bleed::update_bleeding,
+                damage_over_time::tick_damage_over_time,
+                damage_over_time::while_damaged_over_time,
+            )
+                .chain(),
         )
             .in_set(InGameSystems::Simulation),
     )
@@ -46,3 +63,15 @@ pub struct Statuses(Vec<Entity>);
 
 #[derive(Component, Clone)]
 struct StatusApplied;
+
+/// Clones each effect "template" into a status on `target`
+pub fn apply_effects(commands: &mut Commands, effects: &Effects, target: Entity) {
+    trace!("Applying effects: {:?}", effects);
+    effects.iter().for_each(|e| {
+        commands
+            .entity(e)
+            .clone_and_spawn()
+            .remove::<(Disabled, EffectOf)>()
+            .insert(StatusOf(target));
+    });
+}
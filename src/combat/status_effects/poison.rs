@@ -0,0 +1,115 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use crate::{
+    combat::status_effects::{StatusApplied, StatusOf, damage_over_time::DamageOverTime},
+    prelude::*,
+};
+
+/// Poison stacks instead of applying a new status each hit. Each stack adds the base
+/// `DamageOverTime` damage again and refreshes the duration of the poison. Poison seeps through
+/// armor.
+#[derive(Component, Clone)]
+#[require(DamageOverTime::new(1.0, 1.0), IgnoresArmor)]
+pub struct Poisoned {
+    pub max_stacks: u32,
+    stacks: u32,
+    vfx: Option<Entity>,
+}
+
+impl Poisoned {
+    pub fn new(max_stacks: u32) -> Self {
+        Self {
+            max_stacks,
+            stacks: 1,
+            vfx: None,
+        }
+    }
+
+    pub fn stacks(&self) -> u32 {
+        self.stacks
+    }
+}
+
+impl Default for Poisoned {
+    fn default() -> Self {
+        Self::new(5)
+    }
+}
+
+pub(super) fn apply_poisoned(
+    mut commands: Commands,
+    mut new_poison_query: Query<
+        (Entity, &StatusOf, &mut Poisoned, &Lifespan),
+        Without<StatusApplied>,
+    >,
+    mut applied_poison_query: Query<
+        (&StatusOf, &mut Poisoned, &mut DamageOverTime, &mut Lifespan),
+        With<StatusApplied>,
+    >,
+    mut vfx_query: Query<&mut Lifespan, Without<Poisoned>>,
+    sprites: Res<SpriteAssets>,
+    sprite_layouts: Res<SpriteSheetLayouts>,
+) {
+    // Targets that received their first poison this frame, further poisons stack onto it next frame
+    let mut newly_poisoned = HashSet::new();
+
+    for (status, status_of, mut new_poison, new_lifespan) in &mut new_poison_query {
+        if let Some((_, mut poisoned, mut dot, mut lifespan)) = applied_poison_query
+            .iter_mut()
+            .find(|(applied_status_of, ..)| applied_status_of.0 == status_of.0)
+        {
+            poisoned.stacks = (poisoned.stacks + 1).min(poisoned.max_stacks);
+            dot.multiplier = poisoned.stacks as f32;
+            lifespan.0 = new_lifespan.0.clone();
+
+            if let Some(vfx) = poisoned.vfx
+                && let Ok(mut vfx_lifespan) = vfx_query.get_mut(vfx)
+            {
+                vfx_lifespan.0 = new_lifespan.0.clone();
+            }
+
+            commands.entity(status).despawn();
+        } else if newly_poisoned.insert(status_of.0) {
+            let vfx = commands
+                .spawn((
+                    poison_vfx(&sprites, &sprite_layouts, new_lifespan.0.clone()),
+                    ChildOf(status_of.0),
+                ))
+                .id();
+
+            new_poison.vfx = Some(vfx);
+            commands.entity(status).insert(StatusApplied);
+        }
+    }
+}
+
+fn poison_vfx(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    duration: Timer,
+) -> impl Bundle {
+    (
+        Sprite {
+            color: Color::srgba(0.4, 1.0, 0.3, 0.8),
+            ..Sprite::from_atlas_image(
+                sprites.flame.clone(),
+                TextureAtlas {
+                    layout: sprite_layouts.flame_vfx.clone(),
+                    ..default()
+                },
+            )
+        },
+        Transform {
+            translation: Vec3::new(
+                0.0,
+                CHARACTER_FEET_POS_OFFSET + 8.0,
+                ZLayer::SpriteForeground.z(),
+            ),
+            scale: Vec3::new(0.8, 0.8, 1.0),
+            ..default()
+        },
+        AnimationIndices::Cycle((0..=7).cycle()),
+        AnimationTimer(Timer::from_seconds(0.15, TimerMode::Repeating)),
+        Lifespan(duration),
+    )
+}
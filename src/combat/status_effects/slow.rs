@@ -16,7 +16,7 @@ impl Default for Slowed {
     }
 }
 
-pub fn apply_slowed(
+pub(super) fn apply_slowed(
     mut commands: Commands,
     status_query: Query<(Entity, &StatusOf, &Slowed), Without<StatusApplied>>,
     mut motion_query: Query<&mut SimpleMotion>,
@@ -30,7 +30,7 @@ pub fn apply_slowed(
     });
 }
 
-pub fn on_slow_removed(
+pub(super) fn on_slow_removed(
     slow_status: On<Remove, Slowed>,
     status_query: Query<&StatusOf, With<Slowed>>,
     mut motion_query: Query<&mut SimpleMotion>,
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::{
+    combat::status_effects::{StatusApplied, StatusOf, Statuses, slow::Slowed},
+    prelude::*,
+};
+
+/// Stunned characters can't move or use their equipment
+#[derive(Component, Clone, Default)]
+pub struct Stunned;
+
+/// Whether any of the given statuses is a `Stunned` status
+pub fn is_stunned(statuses: Option<&Statuses>, stunned_query: &Query<(), With<Stunned>>) -> bool {
+    statuses.is_some_and(|statuses| statuses.iter().any(|status| stunned_query.contains(status)))
+}
+
+pub(super) fn apply_stunned(
+    mut commands: Commands,
+    status_query: Query<(Entity, &StatusOf, &Lifespan), (With<Stunned>, Without<StatusApplied>)>,
+) {
+    status_query
+        .iter()
+        .for_each(|(status, status_of, duration)| {
+            commands.entity(status).insert(StatusApplied);
+
+            let slowed = commands
+                .spawn((
+                    Slowed { percent: 1.0 },
+                    // make sure slow lasts while stunned
+                    Lifespan::new(duration.0.remaining_secs()),
+                ))
+                .id();
+
+            commands
+                .entity(status_of.0)
+                .add_one_related::<StatusOf>(slowed)
+                .with_child(stun_vfx(duration.0.remaining_secs()));
+        });
+}
+
+fn stun_vfx(duration: f32) -> impl Bundle {
+    (
+        Text2d::new("* * *"),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor::from(Color::srgb(1.0, 0.9, 0.2)),
+        Transform::from_xyz(0.0, 30.0, ZLayer::SpriteForeground.z()),
+        Lifespan::new(duration),
+    )
+}
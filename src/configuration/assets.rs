@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
-use crate::prelude::AppState;
+use crate::prelude::{AppState, BehaviorTreeAsset};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_loading_state(
@@ -10,7 +10,8 @@ pub(super) fn plugin(app: &mut App) {
             .load_collection::<SpriteAssets>()
             .load_collection::<SpriteSheetLayouts>()
             .load_collection::<GameIcons>()
-            .load_collection::<Shadows>(),
+            .load_collection::<Shadows>()
+            .load_collection::<BehaviorTrees>(),
     );
 }
 
@@ -61,6 +62,10 @@ pub struct SpriteAssets {
     pub fire_staff: Handle<Image>,
     #[asset(path = "items/ice_staff.png")]
     pub ice_staff: Handle<Image>,
+    #[asset(path = "items/staff3.png")]
+    pub arcane_staff: Handle<Image>,
+    #[asset(path = "items/staff4.png")]
+    pub meteor_staff: Handle<Image>,
     #[asset(path = "items/health_potion.png")]
     pub health_potion: Handle<Image>,
     #[asset(path = "projectiles/IceBolt.png")]
@@ -99,6 +104,8 @@ pub struct SpriteAssets {
     pub warrior_enemy_sprite_sheet: Handle<Image>,
     #[asset(path = "enemies/fire_mage_enemy.png")]
     pub fire_mage_enemy_sprite_sheet: Handle<Image>,
+    #[asset(path = "enemies/bat.png")]
+    pub bat_enemy_sprite_sheet: Handle<Image>,
     #[asset(path = "npcs/shop_keeper.png")]
     pub shop_keeper_sprite_sheet: Handle<Image>,
     #[asset(path = "npcs/game_guide.png")]
@@ -121,6 +128,20 @@ pub struct GameIcons {
     pub staff_icon: Handle<Image>,
 }
 
+#[derive(AssetCollection, Resource)]
+pub struct BehaviorTrees {
+    #[asset(path = "config/behaviors/melee_enemy.behavior.ron")]
+    pub melee_enemy: Handle<BehaviorTreeAsset>,
+    #[asset(path = "config/behaviors/ranged_enemy.behavior.ron")]
+    pub ranged_enemy: Handle<BehaviorTreeAsset>,
+    #[asset(path = "config/behaviors/bat.behavior.ron")]
+    pub bat: Handle<BehaviorTreeAsset>,
+    #[asset(path = "config/behaviors/villager.behavior.ron")]
+    pub villager: Handle<BehaviorTreeAsset>,
+    #[asset(path = "config/behaviors/companion.behavior.ron")]
+    pub companion: Handle<BehaviorTreeAsset>,
+}
+
 #[derive(AssetCollection, Resource)]
 pub struct Shadows {
     pub character_shadow: ShadowMesh<14, 6>,
@@ -0,0 +1,662 @@
+use std::{collections::HashMap, fmt::Write};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::prelude::*;
+
+/// Number of recent entries shown in the debug panel
+const PANEL_ENTRIES: usize = 12;
+
+/// Opt-in recorder of everything that happens in combat, used for balancing. Toggle recording with
+/// the period key, and switch between CSV and JSON exports with the slash key. Each zone's log and
+/// summary are written to `combat_logs/` when the zone is cleaned up.
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CombatLog>()
+        .add_systems(
+            Update,
+            (
+                handle_combat_log_input
+                    .in_set(InGameSystems::PlayerInput)
+                    .ambiguous_with_all(),
+                update_combat_log_panel.in_set(InGameSystems::HudOverlay),
+            ),
+        )
+        .add_observer(record_attempt_damage)
+        .add_observer(record_damage_dealt)
+        .add_observer(record_healed)
+        .add_observer(record_defeated)
+        .add_observer(record_status_applied)
+        .add_observer(record_equipment_use_failed)
+        .add_observer(export_zone_log);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CombatEventKind {
+    AttemptDamage,
+    DamageDealt,
+    Healed,
+    Defeated,
+    StatusApplied,
+    EquipmentUseFailed,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+struct CombatLogEntry {
+    /// Seconds since the app started
+    time: f32,
+    kind: CombatEventKind,
+    /// Character the event happened to
+    target: String,
+    /// Enemy type of the target, if it is an enemy
+    target_type: Option<String>,
+    /// Character responsible, ex. whoever swung the weapon or cast the heal
+    source: Option<String>,
+    /// Weapon, projectile, status or hazard involved
+    item: Option<String>,
+    amount: Option<f32>,
+    detail: Option<String>,
+}
+
+struct ZoneSummary {
+    zone: u32,
+    duration: f32,
+    damage_by_weapon: Vec<(String, f32)>,
+    damage_taken_by_enemy_type: Vec<(String, f32)>,
+    healing: f32,
+    defeats: u32,
+    failed_uses: u32,
+}
+
+#[derive(Resource, Default)]
+struct CombatLog {
+    recording: bool,
+    export_format: ExportFormat,
+    zone: u32,
+    zone_start: f32,
+    entries: Vec<CombatLogEntry>,
+    /// Summary of the last zone exported, shown in the debug panel
+    last_summary: Option<ZoneSummary>,
+}
+
+impl CombatLog {
+    fn record(&mut self, entry: CombatLogEntry) {
+        self.entries.push(entry);
+    }
+
+    fn summarize(&self, now: f32) -> ZoneSummary {
+        let mut damage_by_weapon: HashMap<String, f32> = HashMap::new();
+        let mut damage_taken_by_enemy_type: HashMap<String, f32> = HashMap::new();
+        let mut healing = 0.0;
+        let mut defeats = 0;
+        let mut failed_uses = 0;
+
+        for entry in &self.entries {
+            match entry.kind {
+                CombatEventKind::DamageDealt => {
+                    let amount = entry.amount.unwrap_or_default();
+                    let weapon = entry.item.clone().unwrap_or_else(|| "Unknown".to_string());
+                    *damage_by_weapon.entry(weapon).or_default() += amount;
+
+                    if let Some(enemy_type) = &entry.target_type {
+                        *damage_taken_by_enemy_type
+                            .entry(enemy_type.clone())
+                            .or_default() += amount;
+                    }
+                }
+                CombatEventKind::Healed => healing += entry.amount.unwrap_or_default(),
+                CombatEventKind::Defeated => defeats += 1,
+                CombatEventKind::EquipmentUseFailed => failed_uses += 1,
+                CombatEventKind::AttemptDamage | CombatEventKind::StatusApplied => {}
+            }
+        }
+
+        ZoneSummary {
+            zone: self.zone,
+            duration: (now - self.zone_start).max(f32::EPSILON),
+            damage_by_weapon: sorted_totals(damage_by_weapon),
+            damage_taken_by_enemy_type: sorted_totals(damage_taken_by_enemy_type),
+            healing,
+            defeats,
+            failed_uses,
+        }
+    }
+}
+
+fn sorted_totals(totals: HashMap<String, f32>) -> Vec<(String, f32)> {
+    let mut totals: Vec<(String, f32)> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+    totals
+}
+
+/// Resolves entities involved in combat to readable names
+#[derive(SystemParam)]
+struct CombatNames<'w, 's> {
+    names: Query<'w, 's, &'static Name>,
+    enemy_types: Query<'w, 's, &'static EnemyType>,
+    players: Query<'w, 's, (), With<Player>>,
+    hurtboxes: Query<'w, 's, &'static ChildOf, With<HurtBox>>,
+    weapons: Query<'w, 's, &'static ItemOf>,
+    projectiles: Query<'w, 's, (&'static FiredBy, Option<&'static FiredFrom>)>,
+}
+
+impl CombatNames<'_, '_> {
+    fn name(&self, entity: Entity) -> String {
+        if let Ok(name) = self.names.get(entity) {
+            name.to_string()
+        } else if let Ok(enemy_type) = self.enemy_types.get(entity) {
+            format!("{enemy_type:?}")
+        } else if self.players.contains(entity) {
+            "Player".to_string()
+        } else {
+            entity.to_string()
+        }
+    }
+
+    /// Damage may target a character's hurtbox instead of the character
+    fn character(&self, entity: Entity) -> Entity {
+        self.hurtboxes.get(entity).map_or(entity, ChildOf::parent)
+    }
+
+    fn enemy_type(&self, entity: Entity) -> Option<String> {
+        self.enemy_types
+            .get(entity)
+            .ok()
+            .map(|enemy_type| format!("{enemy_type:?}"))
+    }
+
+    /// Who is responsible for a damage source, and with what
+    fn attribute(&self, damage_source: Option<Entity>) -> (Option<String>, Option<String>) {
+        let Some(damage_source) = damage_source else {
+            return (None, None);
+        };
+
+        if let Ok(item_of) = self.weapons.get(damage_source) {
+            (Some(self.name(item_of.0)), Some(self.name(damage_source)))
+        } else if let Ok((fired_by, fired_from)) = self.projectiles.get(damage_source) {
+            let weapon = fired_from.map_or(damage_source, |fired_from| fired_from.0);
+            (Some(self.name(fired_by.0)), Some(self.name(weapon)))
+        } else {
+            (None, Some(self.name(damage_source)))
+        }
+    }
+}
+
+fn describe_damage(damage: &Damage) -> String {
+    match damage {
+        Damage::Single(amount) => format!("{amount:.1}"),
+        Damage::Range((min, max)) => format!("{min:.1}-{max:.1}"),
+    }
+}
+
+fn record_attempt_damage(
+    attempt_damage: On<AttemptDamage>,
+    mut log: ResMut<CombatLog>,
+    names: CombatNames,
+    time: Res<Time>,
+) {
+    if !log.recording {
+        return;
+    }
+
+    let target = names.character(attempt_damage.entity);
+    let (source, item) = names.attribute(attempt_damage.damage_source);
+
+    log.record(CombatLogEntry {
+        time: time.elapsed_secs(),
+        kind: CombatEventKind::AttemptDamage,
+        target: names.name(target),
+        target_type: names.enemy_type(target),
+        source,
+        item,
+        amount: None,
+        detail: Some(describe_damage(&attempt_damage.damage)),
+    });
+}
+
+fn record_damage_dealt(
+    damage_dealt: On<DamageDealt>,
+    mut log: ResMut<CombatLog>,
+    names: CombatNames,
+    time: Res<Time>,
+) {
+    if !log.recording {
+        return;
+    }
+
+    let (source, item) = names.attribute(damage_dealt.damage_source);
+
+    log.record(CombatLogEntry {
+        time: time.elapsed_secs(),
+        kind: CombatEventKind::DamageDealt,
+        target: names.name(damage_dealt.entity),
+        target_type: names.enemy_type(damage_dealt.entity),
+        source,
+        item,
+        amount: Some(damage_dealt.damage),
+        detail: None,
+    });
+}
+
+fn record_healed(
+    healed: On<Healed>,
+    mut log: ResMut<CombatLog>,
+    names: CombatNames,
+    time: Res<Time>,
+) {
+    if !log.recording {
+        return;
+    }
+
+    log.record(CombatLogEntry {
+        time: time.elapsed_secs(),
+        kind: CombatEventKind::Healed,
+        target: names.name(healed.entity),
+        target_type: names.enemy_type(healed.entity),
+        source: healed.healer.map(|healer| names.name(healer)),
+        item: None,
+        amount: Some(healed.amount),
+        detail: None,
+    });
+}
+
+fn record_defeated(
+    defeated: On<Defeated>,
+    mut log: ResMut<CombatLog>,
+    names: CombatNames,
+    time: Res<Time>,
+) {
+    if !log.recording {
+        return;
+    }
+
+    log.record(CombatLogEntry {
+        time: time.elapsed_secs(),
+        kind: CombatEventKind::Defeated,
+        target: names.name(defeated.entity),
+        target_type: names.enemy_type(defeated.entity),
+        source: None,
+        item: None,
+        amount: None,
+        detail: None,
+    });
+}
+
+fn record_status_applied(
+    status_added: On<Add, StatusOf>,
+    mut log: ResMut<CombatLog>,
+    names: CombatNames,
+    status_query: Query<(
+        &StatusOf,
+        Has<Burning>,
+        Has<Frozen>,
+        Has<Poisoned>,
+        Has<Bleeding>,
+        Has<Slowed>,
+        Has<Stunned>,
+    )>,
+    time: Res<Time>,
+) {
+    if !log.recording {
+        return;
+    }
+
+    let Ok((status_of, burning, frozen, poisoned, bleeding, slowed, stunned)) =
+        status_query.get(status_added.entity)
+    else {
+        return;
+    };
+
+    let status = if burning {
+        "Burning".to_string()
+    } else if frozen {
+        "Frozen".to_string()
+    } else if poisoned {
+        "Poisoned".to_string()
+    } else if bleeding {
+        "Bleeding".to_string()
+    } else if slowed {
+        "Slowed".to_string()
+    } else if stunned {
+        "Stunned".to_string()
+    } else {
+        names.name(status_added.entity)
+    };
+
+    log.record(CombatLogEntry {
+        time: time.elapsed_secs(),
+        kind: CombatEventKind::StatusApplied,
+        target: names.name(status_of.0),
+        target_type: names.enemy_type(status_of.0),
+        source: None,
+        item: Some(status),
+        amount: None,
+        detail: None,
+    });
+}
+
+fn record_equipment_use_failed(
+    use_failed: On<EquipmentUseFailed>,
+    mut log: ResMut<CombatLog>,
+    names: CombatNames,
+    time: Res<Time>,
+) {
+    if !log.recording {
+        return;
+    }
+
+    log.record(CombatLogEntry {
+        time: time.elapsed_secs(),
+        kind: CombatEventKind::EquipmentUseFailed,
+        target: names.name(use_failed.holder),
+        target_type: names.enemy_type(use_failed.holder),
+        source: None,
+        item: None,
+        amount: None,
+        detail: Some(format!("{:?}: {:?}", use_failed.slot, use_failed.reason)),
+    });
+}
+
+/// Summarizes and exports the log for the zone being left, then starts a fresh one
+fn export_zone_log(_: On<CleanupZone>, mut log: ResMut<CombatLog>, time: Res<Time>) {
+    let now = time.elapsed_secs();
+
+    if log.recording && !log.entries.is_empty() {
+        let summary = log.summarize(now);
+
+        info!(
+            "Combat log zone {}: {:.1}s, {} defeats, {:.1} healing",
+            summary.zone, summary.duration, summary.defeats, summary.healing
+        );
+        for (weapon, damage) in &summary.damage_by_weapon {
+            info!(
+                "  {weapon}: {damage:.1} damage ({:.1} dps)",
+                damage / summary.duration
+            );
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Err(error) = export::write(&log, &summary) {
+            warn!("Failed to export combat log: {error}");
+        }
+
+        log.last_summary = Some(summary);
+    }
+
+    log.entries.clear();
+    log.zone += 1;
+    log.zone_start = now;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod export {
+    use std::fmt::Write;
+
+    use super::{CombatLog, CombatLogEntry, ExportFormat, ZoneSummary};
+
+    const EXPORT_DIRECTORY: &str = "combat_logs";
+
+    pub(super) fn write(log: &CombatLog, summary: &ZoneSummary) -> std::io::Result<()> {
+        std::fs::create_dir_all(EXPORT_DIRECTORY)?;
+
+        match log.export_format {
+            ExportFormat::Csv => {
+                std::fs::write(
+                    format!("{EXPORT_DIRECTORY}/zone_{}.csv", log.zone),
+                    entries_csv(&log.entries),
+                )?;
+                std::fs::write(
+                    format!("{EXPORT_DIRECTORY}/zone_{}_summary.csv", log.zone),
+                    summary_csv(summary),
+                )
+            }
+            ExportFormat::Json => std::fs::write(
+                format!("{EXPORT_DIRECTORY}/zone_{}.json", log.zone),
+                zone_json(&log.entries, summary),
+            ),
+        }
+    }
+
+    fn csv_field(value: &str) -> String {
+        if value.contains([',', '"', '\n']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn entries_csv(entries: &[CombatLogEntry]) -> String {
+        let mut csv = "time,event,target,target_type,source,item,amount,detail\n".to_string();
+
+        for entry in entries {
+            let _ = writeln!(
+                csv,
+                "{:.3},{:?},{},{},{},{},{},{}",
+                entry.time,
+                entry.kind,
+                csv_field(&entry.target),
+                csv_field(entry.target_type.as_deref().unwrap_or_default()),
+                csv_field(entry.source.as_deref().unwrap_or_default()),
+                csv_field(entry.item.as_deref().unwrap_or_default()),
+                entry
+                    .amount
+                    .map(|amount| format!("{amount:.2}"))
+                    .unwrap_or_default(),
+                csv_field(entry.detail.as_deref().unwrap_or_default()),
+            );
+        }
+
+        csv
+    }
+
+    fn summary_csv(summary: &ZoneSummary) -> String {
+        let mut csv = "category,name,total,per_second\n".to_string();
+
+        for (weapon, damage) in &summary.damage_by_weapon {
+            let _ = writeln!(
+                csv,
+                "damage_by_weapon,{},{damage:.2},{:.2}",
+                csv_field(weapon),
+                damage / summary.duration
+            );
+        }
+        for (enemy_type, damage) in &summary.damage_taken_by_enemy_type {
+            let _ = writeln!(
+                csv,
+                "damage_taken_by_enemy_type,{},{damage:.2},{:.2}",
+                csv_field(enemy_type),
+                damage / summary.duration
+            );
+        }
+        let _ = writeln!(
+            csv,
+            "healing,,{:.2},{:.2}",
+            summary.healing,
+            summary.healing / summary.duration
+        );
+        let _ = writeln!(csv, "defeats,,{},", summary.defeats);
+        let _ = writeln!(csv, "failed_uses,,{},", summary.failed_uses);
+        let _ = writeln!(csv, "duration,,{:.2},", summary.duration);
+
+        csv
+    }
+
+    fn json_string(value: &str) -> String {
+        let mut json = String::with_capacity(value.len() + 2);
+        json.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => json.push_str("\\\""),
+                '\\' => json.push_str("\\\\"),
+                '\n' => json.push_str("\\n"),
+                c if c.is_control() => {
+                    let _ = write!(json, "\\u{:04x}", c as u32);
+                }
+                c => json.push(c),
+            }
+        }
+        json.push('"');
+        json
+    }
+
+    fn json_optional_string(value: Option<&str>) -> String {
+        value.map_or_else(|| "null".to_string(), json_string)
+    }
+
+    fn json_totals(totals: &[(String, f32)], duration: f32) -> String {
+        let totals: Vec<String> = totals
+            .iter()
+            .map(|(name, total)| {
+                format!(
+                    "{{\"name\":{},\"total\":{total:.2},\"per_second\":{:.2}}}",
+                    json_string(name),
+                    total / duration
+                )
+            })
+            .collect();
+
+        format!("[{}]", totals.join(","))
+    }
+
+    fn zone_json(entries: &[CombatLogEntry], summary: &ZoneSummary) -> String {
+        let entries: Vec<String> = entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{{\"time\":{:.3},\"event\":\"{:?}\",\"target\":{},\"target_type\":{},\"source\":{},\"item\":{},\"amount\":{},\"detail\":{}}}",
+                    entry.time,
+                    entry.kind,
+                    json_string(&entry.target),
+                    json_optional_string(entry.target_type.as_deref()),
+                    json_optional_string(entry.source.as_deref()),
+                    json_optional_string(entry.item.as_deref()),
+                    entry
+                        .amount
+                        .map_or_else(|| "null".to_string(), |amount| format!("{amount:.2}")),
+                    json_optional_string(entry.detail.as_deref()),
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"zone\":{},\"summary\":{{\"duration\":{:.2},\"damage_by_weapon\":{},\"damage_taken_by_enemy_type\":{},\"healing\":{:.2},\"defeats\":{},\"failed_uses\":{}}},\"entries\":[{}]}}\n",
+            summary.zone,
+            summary.duration,
+            json_totals(&summary.damage_by_weapon, summary.duration),
+            json_totals(&summary.damage_taken_by_enemy_type, summary.duration),
+            summary.healing,
+            summary.defeats,
+            summary.failed_uses,
+            entries.join(","),
+        )
+    }
+}
+
+#[derive(Component)]
+struct CombatLogPanel;
+
+fn handle_combat_log_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut log: ResMut<CombatLog>,
+    panel_query: Query<Entity, With<CombatLogPanel>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Period) {
+        log.recording = !log.recording;
+
+        if log.recording {
+            commands.spawn((
+                Name::new("Combat Log Panel"),
+                CombatLogPanel,
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: px(40.0),
+                    right: px(10.0),
+                    width: px(420.0),
+                    padding: px(8.0).all(),
+                    ..default()
+                },
+                BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                GlobalZIndex(10),
+                Text::default(),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+            ));
+        } else {
+            for panel in &panel_query {
+                commands.entity(panel).despawn();
+            }
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Slash) {
+        log.export_format = match log.export_format {
+            ExportFormat::Csv => ExportFormat::Json,
+            ExportFormat::Json => ExportFormat::Csv,
+        };
+    }
+}
+
+fn update_combat_log_panel(
+    log: Res<CombatLog>,
+    mut panel_query: Query<&mut Text, With<CombatLogPanel>>,
+    time: Res<Time>,
+) {
+    for mut text in &mut panel_query {
+        let summary = log.summarize(time.elapsed_secs());
+
+        let mut panel = format!(
+            "Combat log - zone {} ({:?} export)\n",
+            log.zone, log.export_format
+        );
+
+        for (weapon, damage) in summary.damage_by_weapon.iter().take(4) {
+            let _ = writeln!(
+                panel,
+                "  {weapon}: {damage:.0} dmg, {:.1} dps",
+                damage / summary.duration
+            );
+        }
+
+        if let Some(last) = &log.last_summary {
+            let _ = writeln!(
+                panel,
+                "Last zone {}: {:.0}s, {} defeats",
+                last.zone, last.duration, last.defeats
+            );
+        }
+
+        panel.push('\n');
+
+        let skip = log.entries.len().saturating_sub(PANEL_ENTRIES);
+        for entry in log.entries.iter().skip(skip) {
+            let _ = write!(
+                panel,
+                "{:>7.2} {:?} {}",
+                entry.time, entry.kind, entry.target
+            );
+            if let Some(source) = &entry.source {
+                let _ = write!(panel, " <- {source}");
+            }
+            if let Some(item) = &entry.item {
+                let _ = write!(panel, " [{item}]");
+            }
+            if let Some(amount) = entry.amount {
+                let _ = write!(panel, " {amount:.1}");
+            }
+            if let Some(detail) = &entry.detail {
+                let _ = write!(panel, " {detail}");
+            }
+            panel.push('\n');
+        }
+
+        text.0 = panel;
+    }
+}
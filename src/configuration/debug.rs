@@ -1,19 +1,31 @@
 use avian2d::prelude::*;
 use bevy::{
-    color::palettes::css::{GREEN, YELLOW},
+    color::palettes::css::{GREEN, ORANGE, PURPLE, RED, SKY_BLUE, WHITE, YELLOW},
     dev_tools::states::log_transitions,
     ecs::schedule::{LogLevel, ScheduleBuildSettings},
     log::{Level, LogPlugin},
     prelude::*,
+    window::PrimaryWindow,
 };
+use bevy_behave::prelude::BehaveCtx;
 
 #[cfg(not(target_arch = "wasm32"))]
 use bevy::dev_tools::fps_overlay::FpsOverlayPlugin;
 
-use crate::prelude::{AppState, InGameSystems, Menu, Vision};
+use crate::prelude::{
+    Anchor, AppState, BehaviorSource, BehaviorTreeAsset, Enemy, EnemyType, InGameSystems, Menu,
+    NavGrid, Noise, Player, PlayerFlowField, RunningBehaviorNode, TargetInfo, Vision,
+};
 
 use super::view;
 
+/// How many tiles around the player the navigation overlay covers
+const NAVIGATION_DEBUG_RADIUS: i32 = 16;
+/// How close the cursor has to be to an enemy to hover or select it
+const PICK_RADIUS: f32 = 32.0;
+/// How long a noise's ring stays on screen, fading out
+const NOISE_RING_SECS: f32 = 0.6;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(
         DefaultPlugins
@@ -47,11 +59,24 @@ pub(super) fn plugin(app: &mut App) {
             handle_debug_input
                 .in_set(InGameSystems::PlayerInput)
                 .ambiguous_with_all(),
-            (view::camera_debug_system, debug_vision)
+            pick_debug_enemy
+                .in_set(InGameSystems::PlayerInput)
+                .run_if(resource_exists::<DebugRenderEnabled>),
+            (
+                view::camera_debug_system,
+                debug_vision,
+                debug_navigation,
+                debug_inspected_enemy,
+                debug_noise,
+                update_behavior_tree_panel,
+            )
                 .in_set(InGameSystems::HudOverlay)
                 .run_if(resource_exists::<DebugRenderEnabled>),
         ),
-    );
+    )
+    .init_resource::<DebugSelection>()
+    .init_resource::<NoiseRings>()
+    .add_observer(record_noise);
 
     app.add_systems(Update, log_transitions::<AppState>);
     app.add_systems(Update, log_transitions::<Menu>);
@@ -63,17 +88,71 @@ pub(super) fn plugin(app: &mut App) {
 #[derive(Resource)]
 struct DebugRenderEnabled;
 
+/// Enemy shown in the behavior tree panel. A clicked enemy stays selected, otherwise whichever
+/// enemy is under the cursor is shown
+#[derive(Resource, Default)]
+struct DebugSelection {
+    selected: Option<Entity>,
+    hovered: Option<Entity>,
+}
+
+impl DebugSelection {
+    fn inspected(&self) -> Option<Entity> {
+        self.selected.or(self.hovered)
+    }
+}
+
+/// Recent noises, drawn as rings the size of the area they can be heard in
+#[derive(Resource, Default)]
+struct NoiseRings(Vec<(Noise, Timer)>);
+
+/// Lists the inspected enemy's behavior tree with the running nodes highlighted
+#[derive(Component, Default)]
+struct BehaviorTreePanel {
+    /// Lines currently shown and whether they're highlighted, so spans are only rebuilt on change
+    lines: Vec<(String, bool)>,
+}
+
 fn handle_debug_input(
     mut commands: Commands,
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut config_store: ResMut<GizmoConfigStore>,
+    mut selection: ResMut<DebugSelection>,
+    mut noise_rings: ResMut<NoiseRings>,
     debug_enabled: Option<Res<DebugRenderEnabled>>,
+    panel_query: Query<Entity, With<BehaviorTreePanel>>,
 ) {
     if keyboard_input.just_pressed(KeyCode::Comma) {
         if debug_enabled.is_some() {
             commands.remove_resource::<DebugRenderEnabled>();
+
+            *selection = DebugSelection::default();
+            noise_rings.0.clear();
+            for panel in &panel_query {
+                commands.entity(panel).despawn();
+            }
         } else {
             commands.insert_resource(DebugRenderEnabled);
+
+            commands.spawn((
+                Name::new("Behavior Tree Panel"),
+                BehaviorTreePanel::default(),
+                Node {
+                    position_type: PositionType::Absolute,
+                    bottom: px(10.0),
+                    left: px(10.0),
+                    width: px(300.0),
+                    padding: px(8.0).all(),
+                    ..default()
+                },
+                BackgroundColor::from(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                GlobalZIndex(10),
+                Text::default(),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+            ));
         }
         let config = config_store.config_mut::<PhysicsGizmos>().0;
         config.enabled = !config.enabled;
@@ -95,3 +174,221 @@ fn debug_vision(mut gizmos: Gizmos, query: Query<(&Transform, &Vision)>) {
         gizmos.line_2d(origin, origin + right * 64.0, YELLOW);
     }
 }
+
+/// Draws blocked tiles around the player, and which way the player flow field points on open ones.
+fn debug_navigation(
+    mut gizmos: Gizmos,
+    grid: Option<Res<NavGrid>>,
+    flow_field: Option<Res<PlayerFlowField>>,
+    player: Single<&Transform, With<Player>>,
+) {
+    let Some(grid) = grid else {
+        return;
+    };
+
+    let player_tile = grid.world_to_tile(player.translation.xy());
+    let tile_size = grid.tile_size();
+
+    for x in -NAVIGATION_DEBUG_RADIUS..=NAVIGATION_DEBUG_RADIUS {
+        for y in -NAVIGATION_DEBUG_RADIUS..=NAVIGATION_DEBUG_RADIUS {
+            let tile = player_tile + IVec2::new(x, y);
+            let center = grid.tile_center(tile);
+
+            if !grid.is_walkable(tile) {
+                gizmos.rect_2d(center, tile_size * 0.9, RED);
+            } else if let Some(flow_field) = &flow_field
+                && let Some(next_tile) = flow_field.next_tile(&grid, tile)
+            {
+                let direction = (grid.tile_center(next_tile) - center).normalize_or_zero();
+                gizmos.arrow_2d(center, center + direction * tile_size.x * 0.4, SKY_BLUE);
+            }
+        }
+    }
+}
+
+fn record_noise(
+    noise: On<Noise>,
+    mut noise_rings: ResMut<NoiseRings>,
+    debug_enabled: Option<Res<DebugRenderEnabled>>,
+) {
+    if debug_enabled.is_some() {
+        noise_rings.0.push((
+            *noise.event(),
+            Timer::from_seconds(NOISE_RING_SECS, TimerMode::Once),
+        ));
+    }
+}
+
+/// Draws a fading ring for every recent noise, showing how far away it could be heard
+fn debug_noise(mut gizmos: Gizmos, mut noise_rings: ResMut<NoiseRings>, time: Res<Time>) {
+    noise_rings.0.retain_mut(|(noise, timer)| {
+        timer.tick(time.delta());
+        gizmos.circle_2d(
+            noise.position,
+            noise.radius,
+            PURPLE.with_alpha(timer.fraction_remaining()),
+        );
+        !timer.is_finished()
+    });
+}
+
+/// Hovers the enemy under the cursor, and selects it when clicked. Clicking empty ground clears the
+/// selection.
+fn pick_debug_enemy(
+    mut selection: ResMut<DebugSelection>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+) {
+    let (camera, camera_transform) = *camera;
+
+    let cursor_position = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok());
+
+    selection.hovered = cursor_position.and_then(|cursor| {
+        enemy_query
+            .iter()
+            .map(|(enemy, transform)| (enemy, transform.translation.xy().distance(cursor)))
+            .filter(|(_, distance)| *distance <= PICK_RADIUS)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(enemy, _)| enemy)
+    });
+
+    if mouse_input.just_pressed(MouseButton::Left) {
+        selection.selected = selection.hovered;
+    }
+}
+
+/// Circles the inspected enemy and draws how far it's allowed to stray from its anchor
+fn debug_inspected_enemy(
+    mut gizmos: Gizmos,
+    selection: Res<DebugSelection>,
+    enemy_query: Query<(&Transform, Option<&Anchor>)>,
+) {
+    let Some((transform, anchor)) = selection
+        .inspected()
+        .and_then(|enemy| enemy_query.get(enemy).ok())
+    else {
+        return;
+    };
+
+    gizmos.circle_2d(transform.translation.xy(), PICK_RADIUS, WHITE);
+
+    if let Some(anchor) = anchor {
+        gizmos.circle_2d(anchor.origin(), anchor.radius(), ORANGE);
+        gizmos.line_2d(anchor.origin(), transform.translation.xy(), ORANGE);
+    }
+}
+
+fn update_behavior_tree_panel(
+    mut commands: Commands,
+    selection: Res<DebugSelection>,
+    mut panel: Single<(Entity, &mut BehaviorTreePanel)>,
+    enemy_query: Query<(
+        Option<&EnemyType>,
+        Option<&BehaviorSource>,
+        Option<&TargetInfo>,
+        Option<&Anchor>,
+        &Transform,
+    )>,
+    running_query: Query<(&BehaveCtx, &RunningBehaviorNode)>,
+    tree_assets: Res<Assets<BehaviorTreeAsset>>,
+) {
+    let mut lines = Vec::new();
+
+    match selection
+        .inspected()
+        .and_then(|enemy| Some((enemy, enemy_query.get(enemy).ok()?)))
+    {
+        None => lines.push(("Hover or click an enemy\n".to_string(), false)),
+        Some((enemy, (enemy_type, source, target_info, anchor, transform))) => {
+            let pinned = if selection.selected == Some(enemy) {
+                " (selected)"
+            } else {
+                ""
+            };
+            let enemy_type = enemy_type
+                .map(|enemy_type| format!(" {enemy_type:?}"))
+                .unwrap_or_default();
+            lines.push((format!("{enemy}{enemy_type}{pinned}\n"), false));
+
+            match source.and_then(|source| tree_assets.get(&source.0)) {
+                None => lines.push(("  no behavior tree asset\n".to_string(), false)),
+                Some(asset) => {
+                    let outline = asset.outline();
+                    let mut running = vec![false; outline.len()];
+
+                    for (ctx, node) in &running_query {
+                        if ctx.target_entity() != enemy || node.0 >= outline.len() {
+                            continue;
+                        }
+
+                        // Parents of a running node are running too, they're the closest earlier
+                        // line one level shallower
+                        running[node.0] = true;
+                        let mut depth = outline[node.0].0;
+                        for index in (0..node.0).rev() {
+                            if outline[index].0 < depth {
+                                running[index] = true;
+                                depth = outline[index].0;
+                            }
+                        }
+                    }
+
+                    for ((depth, label), running) in outline.into_iter().zip(running) {
+                        let marker = if running { "> " } else { "  " };
+                        lines.push((format!("{marker}{}{label}\n", "  ".repeat(depth)), running));
+                    }
+                }
+            }
+
+            if let Some(target_info) = target_info {
+                lines.push((
+                    format!(
+                        "Target: {:.0}px, line of sight {}, in cone {}\n",
+                        target_info.distance, target_info.line_of_sight, target_info.in_vision_cone
+                    ),
+                    false,
+                ));
+                if let Some(position) = target_info.last_known_position {
+                    lines.push((format!("Last seen at {position:.0}\n"), false));
+                }
+            }
+
+            if let Some(anchor) = anchor {
+                lines.push((
+                    format!(
+                        "Anchor: {:.0} / {:.0}px",
+                        anchor.distance_from(transform),
+                        anchor.radius()
+                    ),
+                    false,
+                ));
+            }
+        }
+    }
+
+    let (panel_entity, panel) = &mut *panel;
+    if panel.lines == lines {
+        return;
+    }
+
+    commands
+        .entity(*panel_entity)
+        .despawn_related::<Children>()
+        .with_children(|parent| {
+            for (line, running) in &lines {
+                parent.spawn((
+                    TextSpan::new(line.clone()),
+                    TextFont {
+                        font_size: 12.0,
+                        ..default()
+                    },
+                    TextColor(if *running { GREEN.into() } else { Color::WHITE }),
+                ));
+            }
+        });
+    panel.lines = lines;
+}
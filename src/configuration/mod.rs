@@ -1,5 +1,7 @@
 mod assets;
 #[cfg(feature = "dev")]
+mod combat_log;
+#[cfg(feature = "dev")]
 pub mod debug;
 mod physics;
 mod schedule;
@@ -30,7 +32,7 @@ pub(super) fn plugin(app: &mut App) {
     ));
 
     #[cfg(feature = "dev")]
-    app.add_plugins(debug::plugin);
+    app.add_plugins((debug::plugin, combat_log::plugin));
 
     #[cfg(not(feature = "dev"))]
     app.add_plugins(
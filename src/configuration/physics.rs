@@ -19,8 +19,12 @@ pub enum GameCollisionLayer {
 
     // For dealing and taking damage
     HitBox,
-    AllyHurtBox,
+    PlayerHurtBox,
     EnemyHurtBox,
+    VillagerHurtBox,
+    WildlifeHurtBox,
+    /// Turns projectiles around without being hit or seen like a hurtbox
+    ProjectileReflector,
 
     // For physical collisions
     LowObstacle, // Obstacle that stops ground movement but lets things "fly" over, like projectiles
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+/// Items with this build up charge while their input is held and get used once it's released.
+/// The longer the hold (up to `charge_secs`), the stronger the use.
+#[derive(Component, Clone)]
+pub struct Chargeable {
+    /// Seconds the input has to be held to reach full charge
+    pub charge_secs: f32,
+    /// Multiplier applied to the item's damage at full charge
+    pub max_damage_multiplier: f32,
+}
+
+impl Chargeable {
+    pub fn new(charge_secs: f32, max_damage_multiplier: f32) -> Self {
+        Self {
+            charge_secs,
+            max_damage_multiplier,
+        }
+    }
+
+    pub fn damage_multiplier(&self, charge: f32) -> f32 {
+        1.0 + (self.max_damage_multiplier - 1.0) * charge.clamp(0.0, 1.0)
+    }
+}
+
+/// Extra projectiles fired by a charged staff, fanned out around the regular ones
+#[derive(Component, Clone)]
+pub struct ChargedVolley {
+    /// Extra projectiles per projectile at full charge
+    pub extra_projectiles: usize,
+    /// Angle (radians) between each extra projectile
+    pub spread: f32,
+}
+
+/// A charged melee attack lunges the holder forward
+#[derive(Component, Clone)]
+pub struct ChargedLunge {
+    /// Distance covered by a fully charged lunge
+    pub distance: f32,
+}
+
+/// Present on an item while it is being charged up
+#[derive(Component)]
+pub struct Charging(Timer);
+
+impl Charging {
+    pub fn new(chargeable: &Chargeable) -> Self {
+        Self(Timer::from_seconds(chargeable.charge_secs, TimerMode::Once))
+    }
+
+    /// Charge built up so far, from 0.0 to 1.0
+    pub fn fraction(&self) -> f32 {
+        self.0.fraction()
+    }
+}
+
+/// Charge (0.0 to 1.0) the item was released with, inserted right before `UseEquipment` so the
+/// item's observers can scale their effect. Removed again once spent.
+#[derive(Component, Clone, Copy, Default)]
+pub struct Charge(pub f32);
+
+/// Getting stunned drops whatever the character was charging up
+/// Watches `StatusOf` rather than `Stunned`, since cloned stuns get `Stunned` before their holder
+pub(super) fn cancel_charging_on_stun(
+    status_added: On<Add, StatusOf>,
+    mut commands: Commands,
+    status_query: Query<(&StatusOf, Has<Stunned>)>,
+    charging_query: Query<(Entity, &ItemOf), With<Charging>>,
+) {
+    let Ok((status_of, true)) = status_query.get(status_added.entity) else {
+        return;
+    };
+
+    for (item, item_of) in &charging_query {
+        if item_of.0 == status_of.0 {
+            commands.entity(item).remove::<Charging>();
+        }
+    }
+}
+
+pub(super) fn tick_charging(mut charging_query: Query<&mut Charging>, time: Res<Time>) {
+    for mut charging in &mut charging_query {
+        charging.0.tick(time.delta());
+    }
+}
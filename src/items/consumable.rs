@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::prelude::{AttemptHeal, SpriteAssets};
+use crate::prelude::{Armor, AttemptHeal, Lifespan, SpriteAssets, StatusOf};
 
 use super::{Item, ItemType};
 
@@ -10,9 +10,14 @@ pub struct Consumable {
 }
 
 pub enum ConsumableType {
-    Heal(f32), // Heal player for a specific amount
+    Heal(f32),                               // Heal player for a specific amount
+    Fortify { armor: Armor, duration: f32 }, // Grant armor for a number of seconds
 }
 
+/// Saves its holder from defeat, used up automatically instead of being consumed by hand
+#[derive(Component)]
+pub struct ReviveToken;
+
 #[derive(EntityEvent)]
 pub struct Consume {
     pub entity: Entity,
@@ -30,6 +35,37 @@ pub fn health_potion(sprites: &SpriteAssets) -> impl Bundle {
     )
 }
 
+pub fn stoneskin_potion(sprites: &SpriteAssets) -> impl Bundle {
+    (
+        Name::new("Stoneskin Potion"),
+        Item::new(60, ItemType::Potion),
+        Consumable {
+            effect: ConsumableType::Fortify {
+                armor: Armor::new(3.0, 0.25),
+                duration: 20.0,
+            },
+        },
+        Sprite {
+            image: sprites.health_potion.clone(),
+            color: Color::srgb(0.6, 0.6, 0.7),
+            ..default()
+        },
+    )
+}
+
+pub fn revive_token(sprites: &SpriteAssets) -> impl Bundle {
+    (
+        Name::new("Revive Token"),
+        Item::new(200, ItemType::Potion),
+        ReviveToken,
+        Sprite {
+            image: sprites.health_potion.clone(),
+            color: Color::srgb(1.0, 0.84, 0.0),
+            ..default()
+        },
+    )
+}
+
 pub(super) fn on_consume_event(
     consume: On<Consume>,
     mut commands: Commands,
@@ -43,8 +79,17 @@ pub(super) fn on_consume_event(
                 commands.trigger(AttemptHeal {
                     entity: consume.entity,
                     amount: *amount,
+                    healer: Some(consume.entity),
                 });
             }
+            ConsumableType::Fortify { armor, duration } => {
+                commands.spawn((
+                    Name::new("Fortified"),
+                    *armor,
+                    Lifespan::new(*duration),
+                    StatusOf(consume.entity),
+                ));
+            }
         }
         commands.entity(item_entity).despawn();
     }
@@ -5,6 +5,7 @@ use crate::{
     character::Character,
     items::{
         ItemOf,
+        charge::Charging,
         equipment::{Equipped, Mainhand, MainhandOf, Offhand, OffhandOf},
         melee::ActiveMeleeAttack,
     },
@@ -110,6 +111,7 @@ fn on_unequip(
         Equipped,
         Collider,
         ActiveMeleeAttack,
+        Charging,
         MainhandOf,
         OffhandOf,
         ChildOf,
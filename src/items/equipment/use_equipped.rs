@@ -6,7 +6,7 @@ use crate::{
         ItemOf,
         equipment::{Equippable, Equipped},
     },
-    prelude::{EquipmentSlot, Mana, ManaCost},
+    prelude::{EquipmentSlot, Mana, ManaCost, Statuses, Stunned, is_stunned},
 };
 
 // We can use the same event for swords, fists, potions thrown, bows, staffs etc
@@ -25,18 +25,23 @@ pub(super) fn on_ai_equipment_used(
     equipment: On<AIUseEquipment>,
     mut commands: Commands,
     mut equipment_query: Query<EquipmentUsed>,
-    mut holder_query: Query<Option<&mut Mana>, With<Character>>,
+    mut holder_query: Query<(Option<&mut Mana>, Option<&Statuses>), With<Character>>,
+    stunned_query: Query<(), With<Stunned>>,
 ) {
     let Ok(mut equipment_used) = equipment_query.get_mut(equipment.entity) else {
         debug!("AI killed while attempting to use equipment");
         return;
     };
 
-    let Ok(mut mana) = holder_query.get_mut(equipment_used.item_of.0) else {
+    let Ok((mut mana, statuses)) = holder_query.get_mut(equipment_used.item_of.0) else {
         warn!("Non-character attempted to use equipment");
         return;
     };
 
+    if is_stunned(statuses, &stunned_query) {
+        return;
+    }
+
     let use_result = equipment_used.attempt_use(mana.as_deref_mut());
 
     if use_result.is_ok() {
@@ -51,11 +56,12 @@ pub struct StopUsingEquipment {
     pub entity: Entity,
 }
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Debug)]
 pub enum EquipmentUseFailure {
     OutOfMana,
     OnCooldown,
     NoneEquipped,
+    Stunned,
 }
 
 #[derive(EntityEvent)]
@@ -8,7 +8,10 @@ use avian2d::prelude::*;
 use bevy::{platform::collections::HashSet, prelude::*, ui_widgets::observe};
 use bevy_enhanced_input::prelude::*;
 
-use crate::{items::melee::swing::MeleeSwingType, prelude::*};
+use crate::{
+    items::melee::swing::{ComboStep, MeleeSwingType},
+    prelude::*,
+};
 
 /// Our pixel weapons all face upwards currently, so we must rotate them 90 degrees for attacks to
 /// occur in the direction we expect. This value will need to be updated if our assets change
@@ -19,13 +22,20 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        (swing::process_melee_attacks, end_melee_attacks).in_set(InGameSystems::Simulation),
+        (
+            swing::process_melee_attacks,
+            end_melee_attacks,
+            tick_combo_windows,
+        )
+            .in_set(InGameSystems::Simulation),
     );
 
     app.add_systems(
         Update,
         handle_melee_collisions.in_set(InGameSystems::Collision),
     );
+
+    app.add_observer(on_holder_staggered);
 }
 
 pub fn sword(sprites: &SpriteAssets) -> impl Bundle {
@@ -34,11 +44,17 @@ pub fn sword(sprites: &SpriteAssets) -> impl Bundle {
         MeleeWeapon {
             damage: (1.0, 6.0),
             hitbox: Collider::rectangle(10.0, 40.0),
-            attack_type: MeleeSwingType::STAB,
-            attack_time: 0.2,
-            hold_distance: 15.0,
+            combo: vec![
+                ComboStep::new(MeleeSwingType::STAB, 0.2, 15.0),
+                ComboStep::new(MeleeSwingType::SLASH, 0.25, 20.0).with_damage_multiplier(1.2),
+                ComboStep::new(MeleeSwingType::Stab { reach: 45.0 }, 0.35, 15.0)
+                    .with_damage_multiplier(2.0),
+            ],
+            combo_window: 0.5,
         },
         Knockback(10.0),
+        Chargeable::new(1.0, 2.5),
+        ChargedLunge { distance: 80.0 },
         Equippable::default(),
         Item::new(120, ItemType::Melee),
         Sprite::from_image(sprites.sword.clone()),
@@ -53,14 +69,16 @@ pub fn axe(sprites: &SpriteAssets) -> impl Bundle {
         MeleeWeapon {
             damage: (2.0, 12.0),
             hitbox: Collider::rectangle(10.0, 40.0),
-            attack_type: MeleeSwingType::SLASH,
-            attack_time: 0.3,
-            hold_distance: 30.0,
+            combo: axe_combo(),
+            combo_window: 0.6,
         },
         Knockback(20.0),
+        Chargeable::new(1.2, 3.0),
+        ChargedLunge { distance: 48.0 },
         Equippable::default(),
         Sprite::from_image(sprites.axe.clone()),
         Item::new(220, ItemType::Melee),
+        related!(Effects[(Bleeding::default(), Lifespan::new(3.0))]),
         observe(on_melee_equipped),
         observe(on_weapon_melee),
     )
@@ -72,9 +90,8 @@ pub fn freeze_axe(sprites: &SpriteAssets) -> impl Bundle {
         MeleeWeapon {
             damage: (2.0, 12.0),
             hitbox: Collider::rectangle(10.0, 40.0),
-            attack_type: MeleeSwingType::SLASH,
-            attack_time: 0.3,
-            hold_distance: 30.0,
+            combo: axe_combo(),
+            combo_window: 0.6,
         },
         Knockback(2.0),
         Equippable::default(),
@@ -86,23 +103,42 @@ pub fn freeze_axe(sprites: &SpriteAssets) -> impl Bundle {
     )
 }
 
+/// Slash, backslash, then a wide and heavy finishing slash
+fn axe_combo() -> Vec<ComboStep> {
+    vec![
+        ComboStep::new(MeleeSwingType::SLASH, 0.3, 30.0),
+        ComboStep::new(MeleeSwingType::BACKSLASH, 0.3, 30.0),
+        ComboStep::new(
+            MeleeSwingType::Slash {
+                arc_distance: 270f32.to_radians(),
+            },
+            0.45,
+            35.0,
+        )
+        .with_damage_multiplier(2.0),
+    ]
+}
+
 //Repesent a melee weapon
 #[derive(Component, Clone, Debug)]
-#[require(ContextPriority::<MeleeWeapon>::new(1))]
+#[require(ContextPriority::<MeleeWeapon>::new(1), MeleeCombo)]
 struct MeleeWeapon {
-    // Time it takes (seconds) to complete the attack, smaller = faster
-    attack_time: f32,
     damage: (f32, f32),
     hitbox: Collider,
-    attack_type: swing::MeleeSwingType,
-    hold_distance: f32,
+    /// Attacks performed in order, each use of the weapon moves on to the next one
+    combo: Vec<ComboStep>,
+    /// Seconds after an attack ends in which using the weapon again continues the combo
+    combo_window: f32,
 }
 
 impl MeleeWeapon {
-    /// Gets collision layers for melee weapon based on source of damage
-    /// It can either target allies or enemies
-    pub fn collision_layers(damage_source: DamageSource) -> CollisionLayers {
-        CollisionLayers::new(GameCollisionLayer::HitBox, LayerMask::from(damage_source))
+    /// Gets collision layers for melee weapon based on the faction of its holder, it can hit
+    /// anyone that faction is hostile to
+    pub fn collision_layers(faction: Faction, relations: &FactionRelations) -> CollisionLayers {
+        CollisionLayers::new(
+            GameCollisionLayer::HitBox,
+            relations.hostile_hurtboxes(faction),
+        )
     }
 }
 
@@ -110,25 +146,23 @@ fn on_melee_equipped(
     equipped: On<Equip>,
     mut commands: Commands,
     weapon_query: Query<&MeleeWeapon, With<Equippable>>,
-    player: Single<Entity, With<Player>>,
+    holder_query: Query<&Faction>,
+    relations: Res<FactionRelations>,
 ) {
     let Ok(melee_weapon) = weapon_query.get(equipped.item) else {
         error!("on_melee_equipped on wrong type");
         return;
     };
 
-    let is_player = *player == equipped.holder;
-
-    let damage_source = if is_player {
-        DamageSource::Player
-    } else {
-        DamageSource::Enemy
+    let Ok(faction) = holder_query.get(equipped.holder) else {
+        warn!("Melee weapon equipped by holder without a faction");
+        return;
     };
 
     // If melee weapon, we need to add collider and new collision layers on equip
     commands.entity(equipped.item).insert((
         melee_weapon.hitbox.clone(),
-        MeleeWeapon::collision_layers(damage_source),
+        MeleeWeapon::collision_layers(*faction, &relations),
     ));
 }
 
@@ -153,7 +187,9 @@ fn handle_melee_collisions(
                 commands.trigger(AttemptDamage {
                     entity: colliding_entity,
                     ignore_invulnerable: false,
-                    damage: Damage::Range(melee_weapon.damage),
+                    ignore_armor: false,
+                    damage: Damage::Range(melee_weapon.damage)
+                        .scaled(active_melee_attack.step.damage_multiplier),
                     damage_source: Some(weapon_entity),
                     direction: Some(Vec2::from_angle(active_melee_attack.initial_angle)),
                 });
@@ -170,17 +206,39 @@ fn handle_melee_collisions(
 pub struct ActiveMeleeAttack {
     /// Comes from the direction the entity holding the weapon is aiming
     initial_angle: f32,
-    /// Comes from "`attack_speed`" defined on `MeleeWeapon`
+    /// Comes from "`attack_time`" of the current combo step
     duration: Timer,
     entities_damaged: HashSet<Entity>,
+    /// Combo step being performed
+    step: ComboStep,
 }
 
 impl ActiveMeleeAttack {
-    pub fn new(initial_angle: f32, speed: f32) -> Self {
+    fn new(initial_angle: f32, step: ComboStep) -> Self {
         Self {
             initial_angle,
-            duration: Timer::from_seconds(speed, TimerMode::Once),
+            duration: Timer::from_seconds(step.attack_time, TimerMode::Once),
             entities_damaged: HashSet::default(),
+            step,
+        }
+    }
+}
+
+/// Tracks how far into its combo a melee weapon is
+#[derive(Component, Default)]
+pub struct MeleeCombo {
+    next_step: usize,
+    /// Once this finishes the combo starts over, only ticks between attacks
+    window: Timer,
+}
+
+fn tick_combo_windows(
+    mut combo_query: Query<&mut MeleeCombo, Without<ActiveMeleeAttack>>,
+    time: Res<Time>,
+) {
+    for mut combo in &mut combo_query {
+        if combo.window.tick(time.delta()).just_finished() {
+            combo.next_step = 0;
         }
     }
 }
@@ -188,28 +246,84 @@ impl ActiveMeleeAttack {
 fn on_weapon_melee(
     melee: On<UseEquipment>,
     mut commands: Commands,
-    weapon_query: Query<(&ItemOf, &MeleeWeapon)>,
-    mut holder_query: Query<(&mut AttackState, &Vision)>,
+    mut weapon_query: Query<(
+        &ItemOf,
+        &MeleeWeapon,
+        &mut MeleeCombo,
+        Option<(&Chargeable, &Charge)>,
+        Option<&ChargedLunge>,
+    )>,
+    mut holder_query: Query<(&mut AttackState, &Vision, &Faction)>,
+    relations: Res<FactionRelations>,
 ) {
-    let Ok((item_of, melee_weapon)) = weapon_query.get(melee.entity) else {
+    let Ok((item_of, melee_weapon, mut combo, charge, lunge)) = weapon_query.get_mut(melee.entity)
+    else {
         warn!("Tried to melee attack with invalid weapon");
         return;
     };
 
-    let Ok((mut attack_state, vision)) = holder_query.get_mut(item_of.0) else {
-        warn!("Holder missing vision");
+    let Ok((mut attack_state, vision, faction)) = holder_query.get_mut(item_of.0) else {
+        warn!("Holder missing vision or faction");
         return;
     };
 
-    let attack_angle = vision.aim_direction.to_angle();
+    let Some(mut step) = melee_weapon.combo.get(combo.next_step).cloned() else {
+        warn!("Melee weapon has no combo steps");
+        return;
+    };
+
+    // Charged attacks hit harder, and may carry the holder forward with them
+    if let Some((chargeable, charge)) = charge {
+        step.damage_multiplier *= chargeable.damage_multiplier(charge.0);
+
+        if let Some(lunge) = lunge
+            && charge.0 > 0.0
+        {
+            commands.entity(item_of.0).insert(Dashing::new(
+                vision.aim_direction,
+                lunge.distance * charge.0,
+                step.attack_time,
+            ));
+        }
+
+        // Spent on this swing, the next one has to be charged up again
+        commands.entity(melee.entity).remove::<Charge>();
+    }
+
+    attack_state.combo_step = combo.next_step;
     attack_state.is_attacking = true;
 
-    commands.entity(melee.entity).insert(ActiveMeleeAttack::new(
-        attack_angle,
-        melee_weapon.attack_time,
+    combo.next_step = (combo.next_step + 1) % melee_weapon.combo.len();
+    combo.window = Timer::from_seconds(melee_weapon.combo_window, TimerMode::Once);
+
+    let attack_angle = vision.aim_direction.to_angle();
+
+    // Holder may have changed sides since equipping the weapon
+    commands.entity(melee.entity).insert((
+        ActiveMeleeAttack::new(attack_angle, step),
+        MeleeWeapon::collision_layers(*faction, &relations),
     ));
 }
 
+/// Staggered characters drop whatever swing they were in the middle of, and lose their combo
+fn on_holder_staggered(
+    staggered: On<Staggered>,
+    mut commands: Commands,
+    mut weapon_query: Query<(Entity, &ItemOf, &mut MeleeCombo)>,
+    mut attack_state_query: Query<&mut AttackState>,
+) {
+    for (weapon_entity, item_of, mut combo) in &mut weapon_query {
+        if item_of.0 == staggered.entity {
+            commands.entity(weapon_entity).remove::<ActiveMeleeAttack>();
+            combo.next_step = 0;
+        }
+    }
+
+    if let Ok(mut attack_state) = attack_state_query.get_mut(staggered.entity) {
+        attack_state.is_attacking = false;
+    }
+}
+
 fn end_melee_attacks(
     mut commands: Commands,
     query: Query<(Entity, &ChildOf, &ActiveMeleeAttack)>,
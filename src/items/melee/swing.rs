@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use super::{ActiveMeleeAttack, MELEE_WEAPON_ROTATION, MeleeWeapon};
+use super::{ActiveMeleeAttack, MELEE_WEAPON_ROTATION};
 
 #[derive(Debug, Clone)]
 pub(super) enum MeleeSwingType {
@@ -12,6 +12,11 @@ pub(super) enum MeleeSwingType {
         /// Distance we want slash to travel in radians
         arc_distance: f32,
     },
+    /// Slash travelling the opposite way, usually follows up a regular slash
+    Backslash {
+        /// Distance we want slash to travel in radians
+        arc_distance: f32,
+    },
 }
 
 impl MeleeSwingType {
@@ -19,25 +24,57 @@ impl MeleeSwingType {
     pub const SLASH: Self = MeleeSwingType::Slash {
         arc_distance: 180f32.to_radians(),
     };
+    pub const BACKSLASH: Self = MeleeSwingType::Backslash {
+        arc_distance: 180f32.to_radians(),
+    };
+}
+
+/// A single attack in a weapon's combo sequence
+#[derive(Debug, Clone)]
+pub(super) struct ComboStep {
+    pub swing: MeleeSwingType,
+    /// Time it takes (seconds) to complete the attack, smaller = faster
+    pub attack_time: f32,
+    /// Distance from the holder the weapon is swung at
+    pub hold_distance: f32,
+    /// Scales the weapon's damage range
+    pub damage_multiplier: f32,
+}
+
+impl ComboStep {
+    pub const fn new(swing: MeleeSwingType, attack_time: f32, hold_distance: f32) -> Self {
+        Self {
+            swing,
+            attack_time,
+            hold_distance,
+            damage_multiplier: 1.0,
+        }
+    }
+
+    pub const fn with_damage_multiplier(mut self, damage_multiplier: f32) -> Self {
+        self.damage_multiplier = damage_multiplier;
+        self
+    }
 }
 
 /// Determines path of melee weapon based on swing type
 /// TODO: Consider using bevy curve functions to reduce math needed here
 pub(super) fn process_melee_attacks(
     time: Res<Time>,
-    mut attack_query: Query<(&MeleeWeapon, &mut Transform, &mut ActiveMeleeAttack)>,
+    mut attack_query: Query<(&mut Transform, &mut ActiveMeleeAttack)>,
 ) {
-    for (melee_weapon, mut transform, mut active_attack) in &mut attack_query {
+    for (mut transform, mut active_attack) in &mut attack_query {
         active_attack.duration.tick(time.delta());
         let attack_progress = active_attack.duration.fraction();
+        let step = &active_attack.step;
 
-        match melee_weapon.attack_type {
+        match step.swing {
             MeleeSwingType::Stab { reach } => {
                 // Total distance of stab * time of swing gets new position each tick
                 let distance = reach * attack_progress;
 
                 let forward = Vec2::from_angle(active_attack.initial_angle);
-                let new_stab_position = forward * (melee_weapon.hold_distance + distance);
+                let new_stab_position = forward * (step.hold_distance + distance);
 
                 transform.translation = new_stab_position.extend(0.0);
                 transform.rotation =
@@ -48,7 +85,17 @@ pub(super) fn process_melee_attacks(
 
                 let current_angle = start_angle + (arc_distance * attack_progress);
 
-                let new_axe_position = Vec2::from_angle(current_angle) * melee_weapon.hold_distance;
+                let new_axe_position = Vec2::from_angle(current_angle) * step.hold_distance;
+
+                transform.translation = new_axe_position.extend(0.0);
+                transform.rotation = Quat::from_rotation_z(current_angle - MELEE_WEAPON_ROTATION);
+            }
+            MeleeSwingType::Backslash { arc_distance } => {
+                let start_angle = active_attack.initial_angle + (arc_distance / 2.0);
+
+                let current_angle = start_angle - (arc_distance * attack_progress);
+
+                let new_axe_position = Vec2::from_angle(current_angle) * step.hold_distance;
 
                 transform.translation = new_axe_position.extend(0.0);
                 transform.rotation = Quat::from_rotation_z(current_angle - MELEE_WEAPON_ROTATION);
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use crate::prelude::*;
 
+mod charge;
 mod consumable;
 mod equipment;
 mod lootable;
@@ -12,6 +13,7 @@ mod staff;
 mod tome;
 
 pub mod prelude {
+    pub use super::charge::*;
     pub use super::consumable::*;
     pub use super::equipment::prelude::*;
     pub use super::lootable::*;
@@ -35,6 +37,11 @@ pub(super) fn plugin(app: &mut App) {
         FixedUpdate,
         magnet::update_magnet_locations.in_set(MainSystems::InGame),
     )
+    .add_systems(
+        Update,
+        charge::tick_charging.in_set(InGameSystems::Simulation),
+    )
+    .add_observer(charge::cancel_charging_on_stun)
     .add_observer(on_item_added_to_inventory)
     .add_observer(consumable::on_consume_event);
 }
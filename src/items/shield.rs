@@ -1,6 +1,6 @@
 use std::{
     collections::HashSet,
-    f32::consts::{FRAC_PI_2, FRAC_PI_4, PI},
+    f32::consts::{FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, PI},
 };
 
 use avian2d::prelude::*;
@@ -22,10 +22,7 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-pub fn magic_shield(
-    sprites: &Res<SpriteAssets>,
-    sprite_layouts: &Res<SpriteSheetLayouts>,
-) -> impl Bundle {
+pub fn magic_shield(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
     (
         Name::new("Magic Shield"),
         Item::new(355, ItemType::Tome),
@@ -33,6 +30,12 @@ pub fn magic_shield(
         ManaCost(5.0),
         ManaDrainRate(20.0),
         ProjectileReflection,
+        Block {
+            arc: FRAC_PI_4,
+            damage_reduction: 0.5,
+            mana_per_block: 5.0,
+            parry_window: 0.0,
+        },
         Shield {
             hitbox: Collider::rectangle(25.0, 25.0),
         },
@@ -49,10 +52,7 @@ pub fn magic_shield(
     )
 }
 
-pub fn knight_shield(
-    sprites: &Res<SpriteAssets>,
-    sprite_layouts: &Res<SpriteSheetLayouts>,
-) -> impl Bundle {
+pub fn knight_shield(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
     (
         Name::new("Knight Shield"),
         Item::new(355, ItemType::Tome),
@@ -62,6 +62,14 @@ pub fn knight_shield(
         },
         ManaDrainRate(25.0),
         ManaCost(25.0),
+        // Even lowered, a heavy shield soaks up some of every hit
+        Armor::new(1.0, 0.1),
+        Block {
+            arc: FRAC_PI_3,
+            damage_reduction: 1.0,
+            mana_per_block: 10.0,
+            parry_window: 0.2,
+        },
         Sprite {
             image: sprites.knight_shield.clone(),
             texture_atlas: Some(TextureAtlas {
@@ -80,6 +88,33 @@ pub struct Shield {
     pub hitbox: Collider,
 }
 
+/// Damage arriving from in front of a raised shield is reduced, at the cost of the holder's mana
+#[derive(Component, Clone)]
+pub struct Block {
+    /// Half-angle (radians) around the holder's aim direction that the shield covers
+    pub arc: f32,
+    /// Percentage (0.0 to 1.0) of a blocked hit's damage that is negated
+    pub damage_reduction: f32,
+    /// Mana drained from the holder for each blocked hit
+    pub mana_per_block: f32,
+    /// Seconds after raising the shield in which blocking a melee hit stuns the attacker
+    pub parry_window: f32,
+}
+
+impl Block {
+    /// `damage_direction` is the direction the damage travels in, so the shield has to face the opposite way
+    pub fn covers(&self, aim_direction: Vec2, damage_direction: Vec2) -> bool {
+        aim_direction.angle_to(-damage_direction).abs() <= self.arc
+    }
+}
+
+/// Triggered on the shield holder when their shield blocks a hit
+#[derive(EntityEvent)]
+pub struct Blocked {
+    pub entity: Entity,
+    pub parried: bool,
+}
+
 #[derive(Component, Default)]
 #[require(CollidingEntities, Sensor)]
 pub struct ProjectileReflection;
@@ -93,18 +128,36 @@ impl ProjectileReflection {
 #[derive(Component)]
 pub struct ActiveShield {
     pub projectiles_reflected: HashSet<Entity>,
+    /// Blocking before this finishes counts as a parry
+    pub parry_window: Timer,
+}
+
+impl ActiveShield {
+    pub fn is_parrying(&self) -> bool {
+        !self.parry_window.duration().is_zero() && !self.parry_window.is_finished()
+    }
 }
 
 fn update_active_shields(
     mut commands: Commands,
     time: Res<Time>,
     mut active_shield_query: Query<
-        (Entity, &ManaDrainRate, &ItemOf, &mut Sprite),
-        (With<ActiveShield>, With<Equipped>),
+        (
+            Entity,
+            &mut ActiveShield,
+            &ManaDrainRate,
+            &ItemOf,
+            &mut Sprite,
+        ),
+        With<Equipped>,
     >,
     mut holder_query: Query<(&Vision, Option<&mut Mana>)>,
 ) -> Result {
-    for (shield_entity, mana_drain_rate, item_of, mut shield_sprite) in &mut active_shield_query {
+    for (shield_entity, mut active_shield, mana_drain_rate, item_of, mut shield_sprite) in
+        &mut active_shield_query
+    {
+        active_shield.parry_window.tick(time.delta());
+
         let (vision, mana) = holder_query.get_mut(item_of.0)?;
 
         if let Some(mut mana) = mana {
@@ -167,16 +220,19 @@ fn update_active_shields(
 fn on_shield_block(
     used_shield: On<UseEquipment>,
     mut commands: Commands,
-    mut shield_query: Query<&Shield>,
+    mut shield_query: Query<(&Shield, Option<&Block>)>,
 ) {
-    let Ok(shield) = shield_query.get_mut(used_shield.entity) else {
+    let Ok((shield, block)) = shield_query.get_mut(used_shield.entity) else {
         warn!("Tried to block with invalid shield");
         return;
     };
 
+    let parry_window = block.map_or(0.0, |block| block.parry_window);
+
     commands.entity(used_shield.entity).insert((
         ActiveShield {
             projectiles_reflected: HashSet::default(),
+            parry_window: Timer::from_seconds(parry_window, TimerMode::Once),
         },
         shield.hitbox.clone(),
         ProjectileReflection::collision_layers(),
@@ -189,36 +245,44 @@ fn reflect_projectiles(
         With<ProjectileReflection>,
     >,
     mut projectile_query: Query<
-        (&mut LinearVelocity, &mut CollisionLayers, &mut Transform),
+        (
+            &mut LinearVelocity,
+            &mut CollisionLayers,
+            &mut Transform,
+            &mut Faction,
+            &mut FiredBy,
+        ),
         With<Projectile>,
     >,
-    enemy_query: Query<&Enemy>,
+    holder_query: Query<&Faction, Without<Projectile>>,
+    relations: Res<FactionRelations>,
 ) {
     for (mut shield, colliding_entities, child_of) in &mut shield_query {
         for &colliding_entity in colliding_entities.iter() {
             if shield.projectiles_reflected.contains(&colliding_entity) {
                 continue;
             }
-            if let Ok((mut linear_velocity, mut collision_layers, mut transform)) =
-                projectile_query.get_mut(colliding_entity)
+            if let Ok((
+                mut linear_velocity,
+                mut collision_layers,
+                mut transform,
+                mut faction,
+                mut fired_by,
+            )) = projectile_query.get_mut(colliding_entity)
+                && let Ok(holder_faction) = holder_query.get(child_of.parent())
+                // Leave projectiles fired by the holder or its allies alone
+                && relations.is_hostile(*faction, *holder_faction)
             {
-                // If holder is enemy and it is reflected, it can now hurt the player!
-                let new_damage_source = if enemy_query.contains(child_of.parent()) {
-                    DamageSource::Enemy
-                } else {
-                    DamageSource::Player
-                };
-
                 // Reverse direction of projectile! Reflect!
                 linear_velocity.0 = -linear_velocity.0;
 
                 // Rotate projectile sprite to face new velocity direction
                 transform.rotation = Quat::from_rotation_z(linear_velocity.0.to_angle());
 
-                *collision_layers = CollisionLayers::new(
-                    GameCollisionLayer::PROJECTILE_MEMBERSHIPS,
-                    LayerMask::from(new_damage_source) | GameCollisionLayer::HighObstacle,
-                );
+                // Reflected projectiles now belong to the shield holder, and hurt their enemies
+                *collision_layers = Projectile::collision_layers(*holder_faction, &relations);
+                *faction = *holder_faction;
+                fired_by.0 = child_of.parent();
                 shield.projectiles_reflected.insert(colliding_entity);
             }
         }
@@ -1,4 +1,4 @@
-use std::f32::consts::FRAC_PI_8;
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_8};
 
 use avian2d::prelude::*;
 use bevy::{ecs::entity_disabling::Disabled, prelude::*, ui_widgets::observe};
@@ -16,6 +16,11 @@ pub fn fire_staff(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -
         Item::new(1340, ItemType::Staff),
         Equippable::default(),
         ManaCost(6.0),
+        Chargeable::new(1.5, 2.0),
+        ChargedVolley {
+            extra_projectiles: 2,
+            spread: FRAC_PI_8 / 2.0,
+        },
         Sprite::from_image(sprites.fire_staff.clone()),
         related!(
             Projectiles [
@@ -43,39 +48,152 @@ pub fn ice_staff(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) ->
     )
 }
 
+/// Boss version of the fire staff, its fireballs seek out their target and burst on impact
+pub fn cinder_staff(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
+    (
+        Name::new("Staff of Cinders"),
+        Item::new(2200, ItemType::Staff),
+        Equippable::default(),
+        ManaCost(8.0),
+        Sprite::from_image(sprites.fire_staff.clone()),
+        related!(
+            Projectiles [
+                seeking_fireball(sprites, sprite_layouts, -FRAC_PI_8),
+                seeking_fireball(sprites, sprite_layouts, FRAC_PI_8)
+            ]
+        ),
+        observe(on_weapon_fired),
+    )
+}
+
+fn seeking_fireball(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    angle_offset: f32,
+) -> impl Bundle {
+    (
+        fireball(sprites, sprite_layouts, angle_offset),
+        Homing {
+            turn_rate: 2.5,
+            range: 200.0,
+        },
+        Explosive {
+            radius: 40.0,
+            damage: Damage::Range((2.0, 4.0)),
+        },
+    )
+}
+
+/// Boss version of the ice staff, its bolts pierce the first target and shatter into shards
+pub fn shatter_staff(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
+    (
+        Name::new("Staff of Shattering"),
+        Item::new(2600, ItemType::Staff),
+        ManaCost(20.0),
+        Equippable {
+            use_rate: Timer::from_seconds(0.9, TimerMode::Once),
+            ..default()
+        },
+        Sprite::from_image(sprites.ice_staff.clone()),
+        Projectiles::spawn_one((
+            icebolt(sprites, sprite_layouts, 0.0),
+            Pierce(1),
+            SplitOnImpact {
+                count: 3,
+                spread: FRAC_PI_2,
+                lifespan: 0.3,
+            },
+        )),
+        observe(on_weapon_fired),
+    )
+}
+
+pub fn arcane_staff(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
+    (
+        Name::new("Staff of the Arcane"),
+        Item::new(1800, ItemType::Staff),
+        ManaCost(10.0),
+        Equippable {
+            use_rate: Timer::from_seconds(0.5, TimerMode::Once),
+            ..default()
+        },
+        Sprite::from_image(sprites.arcane_staff.clone()),
+        related!(
+            Projectiles [
+                arcane_bolt(sprites, sprite_layouts, -FRAC_PI_8),
+                arcane_bolt(sprites, sprite_layouts, FRAC_PI_8)
+            ]
+        ),
+        observe(on_weapon_fired),
+    )
+}
+
+pub fn meteor_staff(sprites: &SpriteAssets, sprite_layouts: &SpriteSheetLayouts) -> impl Bundle {
+    (
+        Name::new("Staff of Meteors"),
+        Item::new(2400, ItemType::Staff),
+        ManaCost(30.0),
+        Equippable {
+            use_rate: Timer::from_seconds(1.2, TimerMode::Once),
+            ..default()
+        },
+        Chargeable::new(2.0, 2.5),
+        Sprite::from_image(sprites.meteor_staff.clone()),
+        Projectiles::spawn_one(meteor(sprites, sprite_layouts, 0.0)),
+        observe(on_weapon_fired),
+    )
+}
+
 // "fired" implies this is a projectile weapon
 fn on_weapon_fired(
     weapon_fired: On<UseEquipment>,
     mut commands: Commands,
-    weapon_query: Query<(&Projectiles, &ItemOf)>,
-    holder_query: Query<(&Transform, &Vision)>,
-    enemy_query: Query<Entity, With<Enemy>>,
+    weapon_query: Query<(
+        &Projectiles,
+        &ItemOf,
+        Option<(&Chargeable, &Charge)>,
+        Option<&ChargedVolley>,
+    )>,
+    holder_query: Query<(&Transform, &Vision, &Faction)>,
     projectile_query: Query<(&Projectile, Option<&Effects>), With<Disabled>>,
+    relations: Res<FactionRelations>,
 ) {
-    let Ok((projectiles, item_of)) = weapon_query.get(weapon_fired.entity) else {
+    let Ok((projectiles, item_of, charge, volley)) = weapon_query.get(weapon_fired.entity) else {
         warn!("Tried to fire weapon that is not a projectile weapon");
         return;
     };
 
-    let damage_source = if enemy_query.get(item_of.0).is_ok() {
-        DamageSource::Enemy
-    } else {
-        DamageSource::Player
+    let Ok((holder_transform, holder_vision, holder_faction)) = holder_query.get(item_of.0) else {
+        warn!("Tried to fire weapon with holder missing aim position, transform or faction");
+        return;
     };
 
-    let Ok((holder_transform, holder_vision)) = holder_query.get(item_of.0) else {
-        warn!("Tried to fire weapon with holder missing aim position or transform");
-        return;
+    // Charged staffs hit harder, and some fire extra projectiles alongside the regular ones
+    let (damage_multiplier, volley_offsets) = match charge {
+        Some((chargeable, charge)) => (
+            chargeable.damage_multiplier(charge.0),
+            volley.map_or(vec![0.0], |volley| volley_offsets(volley, charge.0)),
+        ),
+        None => (1.0, vec![0.0]),
     };
 
+    // Spent on this volley, the next one has to be charged up again
+    if charge.is_some() {
+        commands.entity(weapon_fired.entity).remove::<Charge>();
+    }
+
     for projectile_entity in projectiles.iter() {
-        if let Ok((projectile, effects)) = projectile_query.get(projectile_entity) {
-            trace!("Spawning projectile with effects: {:?}", effects);
+        let Ok((projectile, effects)) = projectile_query.get(projectile_entity) else {
+            continue;
+        };
 
+        trace!("Spawning projectile with effects: {:?}", effects);
+
+        for volley_offset in &volley_offsets {
             // Rotate the aim direction by the projectile’s angle offset
             let rotated_direction = holder_vision
                 .aim_direction
-                .rotate(Vec2::from_angle(projectile.angle_offset));
+                .rotate(Vec2::from_angle(projectile.angle_offset + volley_offset));
             let starting_position = holder_transform.translation.truncate()
                 + (projectile.forward_offset * rotated_direction);
 
@@ -95,11 +213,27 @@ fn on_weapon_fired(
                         ..default()
                     },
                     LinearVelocity(rotated_direction * projectile.speed),
-                    CollisionLayers::new(
-                        GameCollisionLayer::PROJECTILE_MEMBERSHIPS,
-                        LayerMask::from(damage_source) | GameCollisionLayer::HighObstacle,
-                    ),
+                    Projectile::collision_layers(*holder_faction, &relations),
+                    *holder_faction,
+                    FiredBy(item_of.0),
+                    FiredFrom(weapon_fired.entity),
+                    Projectile {
+                        damage: projectile.damage.scaled(damage_multiplier),
+                        ..projectile.clone()
+                    },
                 ));
         }
     }
 }
+
+/// Angle offsets for each copy of a projectile fired, alternating sides as charge adds more
+fn volley_offsets(volley: &ChargedVolley, charge: f32) -> Vec<f32> {
+    let extra_projectiles = (volley.extra_projectiles as f32 * charge).floor() as usize;
+
+    std::iter::once(0.0)
+        .chain((1..=extra_projectiles).map(|i| {
+            let side = if i % 2 == 1 { 1.0 } else { -1.0 };
+            side * volley.spread * i.div_ceil(2) as f32
+        }))
+        .collect()
+}
@@ -42,6 +42,7 @@ fn on_healing_tome_cast(
     commands.trigger(AttemptHeal {
         entity: item_of.0,
         amount: health_to_add,
+        healer: Some(item_of.0),
     });
     commands
         .entity(item_of.0)
@@ -50,6 +51,44 @@ fn on_healing_tome_cast(
     Ok(())
 }
 
+pub fn tome_of_sanctuary(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+) -> impl Bundle {
+    (
+        Name::new("Tome Of Sanctuary"),
+        Item::new(620, ItemType::Tome),
+        Equippable::from(10.0, EquipmentSlot::Offhand),
+        ManaCost(60.0),
+        Sprite {
+            image: sprites.tome_of_healing.clone(),
+            color: Color::srgb(0.6, 1.0, 0.6),
+            ..default()
+        },
+        related!(GroundEffects[healing_circle(sprites, sprite_layouts)]),
+        observe(on_sanctuary_tome_cast),
+    )
+}
+
+fn on_sanctuary_tome_cast(
+    sanctuary_tome: On<UseEquipment>,
+    mut commands: Commands,
+    tome_query: Query<&ItemOf, With<GroundEffects>>,
+    holder_query: Query<(&Transform, &Faction)>,
+) -> Result {
+    let item_of = tome_query.get(sanctuary_tome.entity)?;
+    let (holder_transform, faction) = holder_query.get(item_of.0)?;
+
+    commands.trigger(SpawnGroundEffects {
+        entity: sanctuary_tome.entity,
+        position: holder_transform.translation.truncate()
+            + Vec2::new(0.0, CHARACTER_FEET_POS_OFFSET),
+        faction: *faction,
+    });
+
+    Ok(())
+}
+
 fn heal_tome_vfx(
     sprites: Res<SpriteAssets>,
     sprite_layouts: Res<SpriteSheetLayouts>,
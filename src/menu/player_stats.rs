@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
-    prelude::{Menu, PlayerStats},
+    prelude::{Menu, Player, PlayerStats, TotalArmor},
     ui::{
         constants::DARK_GRAY_ALPHA_COLOR,
         primitives::{menu_header, text},
@@ -18,8 +18,14 @@ struct StatsMenu;
 #[derive(Component)]
 struct StatsDisplay;
 
-fn spawn_stats_menu(mut commands: Commands, player_stats: Query<&PlayerStats>) {
-    if let Ok(stats) = player_stats.single() {
+fn spawn_stats_menu(
+    mut commands: Commands,
+    player_stats: Query<(Entity, &PlayerStats), With<Player>>,
+    total_armor: TotalArmor,
+) {
+    if let Ok((player, stats)) = player_stats.single() {
+        let armor = total_armor.get(player);
+
         commands.spawn((
             StatsMenu,
             DespawnOnExit(Menu::Stats),
@@ -50,6 +56,15 @@ fn spawn_stats_menu(mut commands: Commands, player_stats: Query<&PlayerStats>) {
                         stat_row("Dexterity", stats.dexterity, "Critical Strike Chance"),
                         stat_row("Intellect", stats.intellect, "Spell damage"),
                         stat_row("Luck", stats.luck, "Drop rate"),
+                        info_row(
+                            "Armor",
+                            format!(
+                                "{:.0} / {:.0}%",
+                                armor.flat,
+                                armor.percent_reduction() * 100.0
+                            ),
+                            "Flat and percent damage reduction",
+                        ),
                     ],
                 )
             ],
@@ -61,6 +76,14 @@ fn stat_row(
     stat_name: impl Into<String>,
     stat_value: u32,
     description: impl Into<String>,
+) -> impl Bundle {
+    info_row(stat_name, format!("{stat_value}/99"), description)
+}
+
+fn info_row(
+    name: impl Into<String>,
+    value: impl Into<String>,
+    description: impl Into<String>,
 ) -> impl Bundle {
     (
         Node {
@@ -82,7 +105,7 @@ fn stat_row(
                     ..default()
                 },
                 children![
-                    text(stat_name, 24.0),
+                    text(name, 24.0),
                     (
                         text(description, 16.0),
                         Node {
@@ -93,7 +116,7 @@ fn stat_row(
                 ]
             ),
             // right side
-            text(format!("{stat_value}/99"), 24.0)
+            text(value, 24.0)
         ],
     )
 }
@@ -1,5 +1,5 @@
 use crate::{
-    prelude::{DisplayableStatType, GameProgress, MainSystems, Menu, PlayerStats},
+    prelude::{DisplayableStatType, ExtraLives, GameProgress, MainSystems, Menu, PlayerStats},
     ui::{
         constants::DARK_GRAY_ALPHA_COLOR,
         primitives::{menu_header, text},
@@ -7,15 +7,23 @@ use crate::{
 };
 use bevy::prelude::*;
 
+/// Progress points needed to buy an extra life for the next run
+const EXTRA_LIFE_COST: u32 = 3;
+const MAX_EXTRA_LIVES: u32 = 3;
+
 pub(super) fn plugin(app: &mut App) {
     app
         // Pause Related Systems
         .add_observer(handle_player_stat_change)
+        .add_observer(handle_extra_life_change)
         .add_observer(handle_stats_shop_ui_update)
         .add_systems(OnEnter(Menu::StatsShop), spawn_stats_shop_menu)
         .add_systems(
             Update,
-            handle_stat_button_interaction
+            (
+                handle_stat_button_interaction,
+                handle_extra_life_button_interaction,
+            )
                 .run_if(in_state(Menu::StatsShop))
                 .in_set(MainSystems::Menu),
         );
@@ -36,15 +44,25 @@ pub struct StatChangeEvent {
     pub is_increase: bool,
 }
 
+#[derive(Component)]
+pub struct ExtraLifeButton {
+    pub is_increase: bool,
+}
+
+#[derive(Event)]
+pub struct ExtraLifeChangeEvent {
+    pub is_increase: bool,
+}
+
 #[derive(Event)]
 pub struct StatsUIUpdateEvent;
 
 pub fn spawn_stats_shop_menu(
     mut commands: Commands,
-    player_stats: Single<&PlayerStats>,
+    player_stats: Single<(&PlayerStats, &ExtraLives)>,
     game_progress: ResMut<GameProgress>,
 ) {
-    let stats = player_stats.into_inner();
+    let (stats, extra_lives) = player_stats.into_inner();
 
     commands.spawn((
         StatShopMenu,
@@ -76,6 +94,7 @@ pub fn spawn_stats_shop_menu(
                     stat_row(DisplayableStatType::Dexterity, stats),
                     stat_row(DisplayableStatType::Intellect, stats),
                     stat_row(DisplayableStatType::Luck, stats),
+                    extra_life_row(extra_lives),
                 ]
             ),
             // Progress Points Display
@@ -126,6 +145,59 @@ fn stat_row(stat_type: DisplayableStatType, stats: &PlayerStats) -> impl Bundle
     )
 }
 
+fn extra_life_row(extra_lives: &ExtraLives) -> impl Bundle {
+    (
+        Node {
+            width: percent(100.0),
+            height: px(50.0),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            padding: px(10.0).horizontal(),
+            ..default()
+        },
+        children![
+            extra_life_button(false),
+            (
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                children![
+                    text(
+                        format!("Extra Lives: {}/{}", extra_lives.0, MAX_EXTRA_LIVES),
+                        24.0
+                    ),
+                    (
+                        text(
+                            format!("Revive on defeat this run ({EXTRA_LIFE_COST} points each)"),
+                            16.0
+                        ),
+                        TextColor::from(Color::srgb(0.5, 0.5, 0.5)),
+                    )
+                ]
+            ),
+            extra_life_button(true)
+        ],
+    )
+}
+
+fn extra_life_button(is_increase: bool) -> impl Bundle {
+    (
+        ExtraLifeButton { is_increase },
+        Button,
+        Node {
+            width: px(30.0),
+            height: px(30.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor::from(Color::srgba(0.2, 0.2, 0.2, 0.5)),
+        children![text(if is_increase { "+" } else { "-" }, 24.0)],
+    )
+}
+
 fn stat_shop_button(stat_type: DisplayableStatType, is_increase: bool) -> impl Bundle {
     (
         StatShopButton {
@@ -180,6 +252,62 @@ pub fn handle_stat_button_interaction(
     }
 }
 
+fn can_buy_extra_life(game_progress: &GameProgress, extra_lives: &ExtraLives) -> bool {
+    game_progress.progress_points >= EXTRA_LIFE_COST && extra_lives.0 < MAX_EXTRA_LIVES
+}
+
+pub fn handle_extra_life_button_interaction(
+    mut interaction_query: Query<(&Interaction, &ExtraLifeButton, &mut BackgroundColor)>,
+    mut commands: Commands,
+    game_progress: Res<GameProgress>,
+    extra_lives: Single<&ExtraLives>,
+) {
+    for (interaction, button, mut background_color) in &mut interaction_query {
+        let can_increase = button.is_increase && can_buy_extra_life(&game_progress, &extra_lives);
+        let can_decrease = !button.is_increase && extra_lives.0 > 0;
+
+        match *interaction {
+            Interaction::Pressed => {
+                if can_increase || can_decrease {
+                    commands.trigger(ExtraLifeChangeEvent {
+                        is_increase: button.is_increase,
+                    });
+                }
+            }
+            Interaction::Hovered => {
+                if can_increase {
+                    *background_color = BackgroundColor(Color::srgb(1.0, 1.0, 0.0));
+                } else {
+                    *background_color = BackgroundColor(Color::srgb(0.5, 0.5, 0.5));
+                }
+            }
+            Interaction::None => {
+                *background_color = BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.5));
+            }
+        }
+    }
+}
+
+pub fn handle_extra_life_change(
+    trigger: On<ExtraLifeChangeEvent>,
+    mut extra_lives: Single<&mut ExtraLives>,
+    mut game_progress: ResMut<GameProgress>,
+    mut commands: Commands,
+) {
+    if trigger.is_increase {
+        if can_buy_extra_life(&game_progress, &extra_lives) {
+            extra_lives.0 += 1;
+            game_progress.progress_points -= EXTRA_LIFE_COST;
+            commands.trigger(StatsUIUpdateEvent);
+        }
+    } else if extra_lives.0 > 0 {
+        // Refunded in full, nothing has been used yet while still in the hub
+        extra_lives.0 -= 1;
+        game_progress.progress_points += EXTRA_LIFE_COST;
+        commands.trigger(StatsUIUpdateEvent);
+    }
+}
+
 pub fn handle_player_stat_change(
     trigger: On<StatChangeEvent>,
     mut player_stats: Query<&mut PlayerStats>,
@@ -222,11 +350,11 @@ pub fn handle_stats_shop_ui_update(
     _: On<StatsUIUpdateEvent>,
     mut commands: Commands,
     stats_menu_query: Query<Entity, With<StatShopMenu>>,
-    player_stats_query: Single<&PlayerStats>,
+    player_stats_query: Single<(&PlayerStats, &ExtraLives)>,
     mut game_progress: ResMut<GameProgress>,
 ) {
     //Set Game Progress to current player stats
-    let player_stats = player_stats_query.clone();
+    let (player_stats, _) = *player_stats_query;
     game_progress.base_stats = player_stats.clone();
 
     // Despawn existing menu
@@ -2,10 +2,12 @@ use avian2d::prelude::ColliderAabb;
 use bevy::prelude::*;
 use rand::Rng;
 
-use crate::prelude::{DamageDealt, Healed, Lifespan, ZLayer};
+use crate::prelude::{Blocked, DamageDealt, Healed, Lifespan, Revived, ZLayer};
 
 const RED_COLOR: Color = Color::srgb(1.0, 0.0, 0.0);
 const GREEN_COLOR: Color = Color::srgb(0.0, 0.8, 0.0);
+const GRAY_COLOR: Color = Color::srgb(0.7, 0.7, 0.7);
+const GOLD_COLOR: Color = Color::srgb(1.0, 0.84, 0.0);
 const HEALTH_TEXT_OFFSET: f32 = 10.0;
 
 fn spawn_health_change_text(
@@ -14,6 +16,23 @@ fn spawn_health_change_text(
     amount: f32,
     color: Color,
     collider_query: &Query<&ColliderAabb>,
+) {
+    let rounded_amount = (amount * 10.0).round() / 10.0; // Round to 1 decimal place
+    let formatted_amount = if rounded_amount.fract() == 0.0 {
+        format!("{rounded_amount:.0}") // Display as a whole number
+    } else {
+        format!("{rounded_amount:.1}") // Display with one decimal place
+    };
+
+    spawn_overlay_text(commands, entity, formatted_amount, color, collider_query);
+}
+
+fn spawn_overlay_text(
+    commands: &mut Commands,
+    entity: Entity,
+    text: String,
+    color: Color,
+    collider_query: &Query<&ColliderAabb>,
 ) {
     let entity_height = if let Ok(collider) = collider_query.get(entity) {
         collider.max.y - collider.min.y
@@ -33,15 +52,8 @@ fn spawn_health_change_text(
     // Scale the direction vector by the desired text height to place the text above the entity
     let text_position = (rotated_vector.normalize() * text_height).extend(ZLayer::AboveSprite.z());
 
-    let rounded_amount = (amount * 10.0).round() / 10.0; // Round to 1 decimal place
-    let formatted_amount = if rounded_amount.fract() == 0.0 {
-        format!("{rounded_amount:.0}") // Display as a whole number
-    } else {
-        format!("{rounded_amount:.1}") // Display with one decimal place
-    };
-
     commands.entity(entity).with_child((
-        Text2d::new(formatted_amount),
+        Text2d::new(text),
         TextColor::from(color),
         Lifespan::new(0.4),
         Transform::from_translation(text_position),
@@ -81,3 +93,37 @@ pub fn on_healing_overlay_amount(
         &healed_query,
     );
 }
+
+pub fn on_blocked_overlay(
+    blocked: On<Blocked>,
+    mut commands: Commands,
+    blocker_query: Query<&ColliderAabb>,
+) {
+    let (text, color) = if blocked.parried {
+        ("Parry!", GOLD_COLOR)
+    } else {
+        ("Blocked", GRAY_COLOR)
+    };
+
+    spawn_overlay_text(
+        &mut commands,
+        blocked.entity,
+        text.to_string(),
+        color,
+        &blocker_query,
+    );
+}
+
+pub fn on_revived_overlay(
+    revived: On<Revived>,
+    mut commands: Commands,
+    revived_query: Query<&ColliderAabb>,
+) {
+    spawn_overlay_text(
+        &mut commands,
+        revived.entity,
+        "Revived!".to_string(),
+        GOLD_COLOR,
+        &revived_query,
+    );
+}
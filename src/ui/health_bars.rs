@@ -0,0 +1,309 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+const HEALTH_BAR_WIDTH: f32 = 32.0;
+const HEALTH_BAR_HEIGHT: f32 = 4.0;
+/// Height above the enemy's center the bar floats at
+const HEALTH_BAR_OFFSET: f32 = 36.0;
+const HEALTH_BAR_BACKGROUND_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, 0.8);
+const HEALTH_BAR_COLOR: Color = Color::srgb(0.8, 0.0, 0.0);
+
+const BOSS_BAR_WIDTH: f32 = 600.0;
+const BOSS_BAR_HEIGHT: f32 = 20.0;
+
+const STATUS_ICON_SIZE: f32 = 6.0;
+const BOSS_STATUS_ICON_SIZE: f32 = 16.0;
+
+/// World-space health bar floating above an enemy, hidden while the enemy is at full health
+#[derive(Component)]
+pub(super) struct EnemyHealthBar;
+
+#[derive(Component)]
+pub(super) struct EnemyHealthBarFill;
+
+#[derive(Component)]
+pub(super) struct BossBar {
+    boss: Entity,
+}
+
+#[derive(Component)]
+pub(super) struct BossBarFill;
+
+/// Row of icons showing the statuses currently on `owner`. Rows that are UI nodes get node icons,
+/// everything else gets sprite icons.
+#[derive(Component)]
+pub(super) struct StatusIcons {
+    owner: Entity,
+    shown: Vec<StatusIcon>,
+}
+
+impl StatusIcons {
+    fn new(owner: Entity) -> Self {
+        Self {
+            owner,
+            shown: Vec::new(),
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum StatusIcon {
+    Burning,
+    Frozen,
+    Poisoned,
+    Bleeding,
+    Slowed,
+    Stunned,
+    Fortified,
+}
+
+impl StatusIcon {
+    fn color(self) -> Color {
+        match self {
+            StatusIcon::Burning => Color::srgb(1.0, 0.5, 0.0),
+            StatusIcon::Frozen => Color::srgb(0.6, 0.9, 1.0),
+            StatusIcon::Poisoned => Color::srgb(0.2, 0.8, 0.2),
+            StatusIcon::Bleeding => Color::srgb(0.6, 0.0, 0.0),
+            StatusIcon::Slowed => Color::srgb(0.3, 0.4, 0.8),
+            StatusIcon::Stunned => Color::srgb(1.0, 0.9, 0.2),
+            StatusIcon::Fortified => Color::srgb(0.6, 0.6, 0.7),
+        }
+    }
+}
+
+pub(super) fn spawn_enemy_health_bars(
+    mut commands: Commands,
+    enemy_query: Query<(Entity, Option<&Boss>), Added<Enemy>>,
+) {
+    for (enemy, boss) in &enemy_query {
+        match boss {
+            // Bosses get their bar across the top of the screen instead
+            Some(boss) => {
+                commands.spawn(boss_bar(enemy, boss));
+            }
+            None => {
+                commands.entity(enemy).with_children(|parent| {
+                    parent.spawn(enemy_health_bar());
+                    parent.spawn((
+                        StatusIcons::new(enemy),
+                        Transform::from_xyz(
+                            -HEALTH_BAR_WIDTH / 2.0,
+                            HEALTH_BAR_OFFSET + HEALTH_BAR_HEIGHT + STATUS_ICON_SIZE / 2.0,
+                            ZLayer::AboveSprite.z(),
+                        ),
+                        Visibility::Inherited,
+                    ));
+                });
+            }
+        }
+    }
+}
+
+fn enemy_health_bar() -> impl Bundle {
+    (
+        EnemyHealthBar,
+        Sprite::from_color(
+            HEALTH_BAR_BACKGROUND_COLOR,
+            Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT),
+        ),
+        Transform::from_xyz(0.0, HEALTH_BAR_OFFSET, ZLayer::AboveSprite.z()),
+        Visibility::Hidden,
+        children![(
+            EnemyHealthBarFill,
+            Sprite::from_color(
+                HEALTH_BAR_COLOR,
+                Vec2::new(HEALTH_BAR_WIDTH, HEALTH_BAR_HEIGHT)
+            ),
+            Transform::from_xyz(0.0, 0.0, 0.1),
+        )],
+    )
+}
+
+fn boss_bar(boss_entity: Entity, boss: &Boss) -> impl Bundle {
+    (
+        BossBar { boss: boss_entity },
+        Name::new("Boss Bar"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(20.0),
+            width: percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(4.0),
+            ..default()
+        },
+        children![
+            (
+                Text::new(boss.name.clone()),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+            ),
+            (
+                Node {
+                    width: px(BOSS_BAR_WIDTH),
+                    height: px(BOSS_BAR_HEIGHT),
+                    ..default()
+                },
+                BackgroundColor::from(HEALTH_BAR_BACKGROUND_COLOR),
+                children![(
+                    BossBarFill,
+                    Node {
+                        width: percent(100.0),
+                        height: percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor::from(HEALTH_BAR_COLOR),
+                )],
+            ),
+            (
+                StatusIcons::new(boss_entity),
+                Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: px(4.0),
+                    ..default()
+                },
+            )
+        ],
+    )
+}
+
+pub(super) fn update_enemy_health_bars(
+    enemy_query: Query<(&Health, &Children), (With<Enemy>, Changed<Health>)>,
+    mut bar_query: Query<(&mut Visibility, &Children), With<EnemyHealthBar>>,
+    mut fill_query: Query<(&mut Sprite, &mut Transform), With<EnemyHealthBarFill>>,
+) {
+    for (health, children) in &enemy_query {
+        for child in children.iter() {
+            let Ok((mut visibility, bar_children)) = bar_query.get_mut(child) else {
+                continue;
+            };
+
+            let damaged = health.hp > 0.0 && health.hp < health.max_hp;
+            visibility.set_if_neq(if damaged {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+
+            let ratio = (health.hp / health.max_hp).clamp(0.0, 1.0);
+
+            for bar_child in bar_children.iter() {
+                if let Ok((mut sprite, mut transform)) = fill_query.get_mut(bar_child) {
+                    // Keep the fill pinned to the left edge as it shrinks
+                    sprite.custom_size =
+                        Some(Vec2::new(HEALTH_BAR_WIDTH * ratio, HEALTH_BAR_HEIGHT));
+                    transform.translation.x = -HEALTH_BAR_WIDTH * (1.0 - ratio) / 2.0;
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn update_boss_bars(
+    mut commands: Commands,
+    boss_bar_query: Query<(Entity, &BossBar)>,
+    boss_query: Query<&Health, With<Boss>>,
+    mut fill_query: Query<&mut Node, With<BossBarFill>>,
+    children_query: Query<&Children>,
+) {
+    for (bar_entity, boss_bar) in &boss_bar_query {
+        let Ok(health) = boss_query.get(boss_bar.boss) else {
+            // Boss is gone, so is their bar
+            commands.entity(bar_entity).despawn();
+            continue;
+        };
+
+        for descendant in children_query.iter_descendants(bar_entity) {
+            if let Ok(mut fill_node) = fill_query.get_mut(descendant) {
+                fill_node.width = percent(100.0 * (health.hp / health.max_hp).clamp(0.0, 1.0));
+            }
+        }
+    }
+}
+
+pub(super) fn update_status_icons(
+    mut commands: Commands,
+    mut icons_query: Query<(Entity, &mut StatusIcons, Has<Node>)>,
+    statuses_query: Query<&Statuses>,
+    status_query: Query<(
+        Has<Burning>,
+        Has<Frozen>,
+        Has<Poisoned>,
+        Has<Bleeding>,
+        Has<Slowed>,
+        Has<Stunned>,
+        Has<Armor>,
+    )>,
+) {
+    for (icons_entity, mut icons, is_ui) in &mut icons_query {
+        let mut active = Vec::new();
+
+        for status in statuses_query
+            .get(icons.owner)
+            .into_iter()
+            .flat_map(RelationshipTarget::iter)
+        {
+            let Ok((burning, frozen, poisoned, bleeding, slowed, stunned, fortified)) =
+                status_query.get(status)
+            else {
+                continue;
+            };
+
+            let icon = if burning {
+                StatusIcon::Burning
+            } else if frozen {
+                StatusIcon::Frozen
+            } else if poisoned {
+                StatusIcon::Poisoned
+            } else if bleeding {
+                StatusIcon::Bleeding
+            } else if slowed {
+                StatusIcon::Slowed
+            } else if stunned {
+                StatusIcon::Stunned
+            } else if fortified {
+                StatusIcon::Fortified
+            } else {
+                continue;
+            };
+
+            if !active.contains(&icon) {
+                active.push(icon);
+            }
+        }
+
+        if active == icons.shown {
+            continue;
+        }
+
+        commands.entity(icons_entity).despawn_related::<Children>();
+        commands.entity(icons_entity).with_children(|parent| {
+            for (i, icon) in active.iter().enumerate() {
+                if is_ui {
+                    parent.spawn((
+                        Node {
+                            width: px(BOSS_STATUS_ICON_SIZE),
+                            height: px(BOSS_STATUS_ICON_SIZE),
+                            ..default()
+                        },
+                        BackgroundColor::from(icon.color()),
+                    ));
+                } else {
+                    parent.spawn((
+                        Sprite::from_color(icon.color(), Vec2::splat(STATUS_ICON_SIZE)),
+                        Transform::from_xyz(
+                            STATUS_ICON_SIZE / 2.0 + i as f32 * (STATUS_ICON_SIZE + 1.0),
+                            0.0,
+                            0.0,
+                        ),
+                    ));
+                }
+            }
+        });
+
+        icons.shown = active;
+    }
+}
@@ -1,6 +1,7 @@
 pub mod constants;
 mod damage_overlay;
 mod game_over_screen;
+mod health_bars;
 mod load_screen;
 pub mod plugin;
 pub mod primitives;
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::{
     prelude::*,
-    ui::{damage_overlay, game_over_screen, load_screen, start_screen},
+    ui::{damage_overlay, game_over_screen, health_bars, load_screen, start_screen},
 };
 
 /// Plugin responsible for managing all UI-related systems and state transitions
@@ -27,7 +27,22 @@ impl Plugin for UIPlugin {
 
         // Heal and damage overlays
         app.add_observer(damage_overlay::on_damage_overlay_amount)
-            .add_observer(damage_overlay::on_healing_overlay_amount);
+            .add_observer(damage_overlay::on_healing_overlay_amount)
+            .add_observer(damage_overlay::on_blocked_overlay)
+            .add_observer(damage_overlay::on_revived_overlay);
+
+        // Enemy and boss health bars
+        app.add_observer(despawn_all::<CleanupZone, health_bars::BossBar>)
+            .add_systems(
+                Update,
+                (
+                    health_bars::spawn_enemy_health_bars,
+                    health_bars::update_enemy_health_bars,
+                    health_bars::update_boss_bars,
+                    health_bars::update_status_icons,
+                )
+                    .in_set(InGameSystems::HudOverlay),
+            );
 
         // Game over systems
         app.add_systems(OnEnter(AppState::GameOver), game_over_screen::spawn);
@@ -7,6 +7,7 @@ use crate::prelude::*;
 /// Center of chest relative to its sprite's anchor point
 const CHEST_HEIGHT_OFFSET: f32 = -8.0;
 const BOTTOM_OF_CHEST: f32 = CHEST_HEIGHT_OFFSET - 8.0;
+const CHEST_NOISE_RADIUS: f32 = 180.0;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(on_spawn_chests_event);
@@ -101,5 +102,12 @@ fn on_interaction_open_chest(
             amount: 999,
             location: chest_transform.translation.truncate(),
         });
+
+        // Nobody owns the chest, so the creak draws in everyone nearby
+        commands.trigger(Noise {
+            position: chest_transform.translation.truncate(),
+            radius: CHEST_NOISE_RADIUS,
+            faction: None,
+        });
     }
 }
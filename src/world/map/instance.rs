@@ -49,6 +49,8 @@ impl InstanceAssets {
         ) as u32;
         let num_chests =
             rng.random_range(instance_type.chest_range.0..=instance_type.chest_range.1) as u32;
+        let num_traps =
+            rng.random_range(instance_type.trap_range.0..=instance_type.trap_range.1) as u32;
 
         let floor_type = match instance_type.floor_type.as_str() {
             "Ground" => TileType::Ground,
@@ -76,8 +78,11 @@ impl InstanceAssets {
             .with_floor(floor_type) //Floor really needs to go first, you don't wanna know what happens if it doesn't
             .with_exterior_walls()
             .with_chests(num_chests)
+            .with_traps(num_traps)
+            .with_bosses(instance_type.num_bosses)
             .with_exits(instance_type.num_exits)
             .with_enemies(num_enemies)
+            .with_elite_chance(instance_type.elite_chance)
             .build();
 
         Ok(MapLayout::from(map_data))
@@ -96,6 +101,9 @@ struct InstanceType {
     pub number_of_enemies_range: (f32, f32),
     pub num_exits: u32,
     pub chest_range: (f32, f32),
+    pub trap_range: (f32, f32),
+    pub num_bosses: u32,
+    pub elite_chance: f32,
     pub prefabs: Vec<String>,
     pub floor_type: String,
 }
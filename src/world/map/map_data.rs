@@ -20,6 +20,8 @@ pub struct MapData {
     pub tiles: Vec<Vec<TileType>>,
     pub colliders: Vec<EnvironmentalMapCollider>,
     pub markers: HashMap<MarkerType, Vec<Vec2>>,
+    /// Chance of each regular enemy spawning as an elite
+    pub elite_chance: f32,
 }
 
 impl MapData {
@@ -29,6 +31,7 @@ impl MapData {
             tiles: vec![vec![floor_type; size.y as usize]; size.x as usize],
             colliders: Vec::new(),
             markers: HashMap::new(),
+            elite_chance: 0.0,
         }
     }
 
@@ -64,6 +67,8 @@ pub struct MapDataBuilder {
     num_enemies: Option<u32>,
     num_exits: u32,
     num_chests: Option<u32>,
+    num_traps: Option<u32>,
+    num_bosses: Option<u32>,
 }
 
 impl MapDataBuilder {
@@ -74,6 +79,8 @@ impl MapDataBuilder {
             prefabs: Vec::new(),
             num_enemies: None,
             num_chests: None,
+            num_traps: None,
+            num_bosses: None,
             num_exits: 0,
         }
     }
@@ -98,6 +105,21 @@ impl MapDataBuilder {
         self
     }
 
+    pub fn with_traps(mut self, count: u32) -> Self {
+        self.num_traps = Some(count);
+        self
+    }
+
+    pub fn with_bosses(mut self, count: u32) -> Self {
+        self.num_bosses = Some(count);
+        self
+    }
+
+    pub fn with_elite_chance(mut self, chance: f32) -> Self {
+        self.map_data.elite_chance = chance;
+        self
+    }
+
     pub fn with_exits(mut self, count: u32) -> Self {
         self.num_exits = count;
         self
@@ -123,6 +145,19 @@ impl MapDataBuilder {
             markers.insert(MarkerType::ChestSpawns, chest_positions);
         }
 
+        if let Some(num_traps) = self.num_traps {
+            let trap_positions =
+                find_multiple_positions(&self.map_data.tiles, self.size, 0.2..0.8, num_traps);
+            markers.insert(MarkerType::TrapSpawns, trap_positions);
+        }
+
+        if let Some(num_bosses) = self.num_bosses {
+            // Bosses wait deeper in the map than regular enemies
+            let boss_positions =
+                find_multiple_positions(&self.map_data.tiles, self.size, 0.6..0.9, num_bosses);
+            markers.insert(MarkerType::BossSpawns, boss_positions);
+        }
+
         // Always generate entrance/exit positions for random sprite_layouts
         let (player_pos, exit_positions) =
             generate_entrance_exit_positions(self.size, self.num_exits);
@@ -1,5 +1,6 @@
 mod instance;
 mod map_data;
+mod navigation;
 mod prefabs;
 mod utils;
 mod walls;
@@ -18,13 +19,14 @@ use crate::{
 
 pub mod prelude {
     pub use super::instance::*;
+    pub use super::navigation::*;
     pub use super::prefabs::*;
     pub use super::zone::*;
     pub use super::*;
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((instance::plugin, zone::plugin))
+    app.add_plugins((instance::plugin, navigation::plugin, zone::plugin))
         .add_systems(OnEnter(AppState::CreateHub), insert_hub_layout)
         .insert_resource(WorldSpaceConfig::default());
 }
@@ -48,6 +50,7 @@ pub enum MarkerType {
     EnemySpawns,
     BossSpawns,
     ChestSpawns,
+    TrapSpawns,
     NPCSpawns,
     PlayerSpawns,
     LevelExits,
@@ -137,6 +140,7 @@ pub struct MapLayout {
     pub tiles: Vec<Vec<TileType>>,
     pub markers: MapMarkers,
     pub environmental_colliders: Vec<EnvironmentalMapCollider>,
+    pub elite_chance: f32,
 }
 
 impl From<MapData> for MapLayout {
@@ -148,6 +152,7 @@ impl From<MapData> for MapLayout {
                 markers: map_data.markers,
             },
             environmental_colliders: map_data.colliders,
+            elite_chance: map_data.elite_chance,
         }
     }
 }
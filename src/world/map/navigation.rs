@@ -0,0 +1,447 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    prelude::*,
+    world::map::{MapLayout, TileType, WorldSpaceConfig},
+};
+
+/// Cost of moving to an orthogonal neighbor, diagonal moves cost `DIAGONAL_COST`
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+/// A* gives up after expanding this many tiles, the character falls back to a straight line
+const MAX_SEARCH_NODES: usize = 4000;
+/// Straight line checks are widened by this much on each side so characters don't clip corners
+const CHARACTER_CLEARANCE: f32 = 12.0;
+/// Goals inside walls are moved to the nearest open tile within this many tiles
+const NEAREST_OPEN_SEARCH_RADIUS: i32 = 3;
+
+const NEIGHBORS: [(IVec2, u32); 8] = [
+    (IVec2::new(1, 0), STRAIGHT_COST),
+    (IVec2::new(-1, 0), STRAIGHT_COST),
+    (IVec2::new(0, 1), STRAIGHT_COST),
+    (IVec2::new(0, -1), STRAIGHT_COST),
+    (IVec2::new(1, 1), DIAGONAL_COST),
+    (IVec2::new(1, -1), DIAGONAL_COST),
+    (IVec2::new(-1, 1), DIAGONAL_COST),
+    (IVec2::new(-1, -1), DIAGONAL_COST),
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(AppState::SpawnZone), build_nav_grid)
+        .add_systems(
+            Update,
+            update_player_flow_field.in_set(InGameSystems::Simulation),
+        );
+}
+
+/// Which tiles of the current zone can be walked on, built from the map layout when the zone spawns
+#[derive(Resource)]
+pub struct NavGrid {
+    width: i32,
+    height: i32,
+    walkable: Vec<bool>,
+    /// World position of the bottom left corner of tile (0, 0)
+    origin: Vec2,
+    tile_size: Vec2,
+}
+
+impl NavGrid {
+    fn new(map_layout: &MapLayout, world_config: &WorldSpaceConfig) -> Self {
+        let width = map_layout.size.x as i32;
+        let height = map_layout.size.y as i32;
+
+        let mut walkable: Vec<bool> = (0..width * height)
+            .map(|index| {
+                let tile = map_layout.tiles[(index % width) as usize][(index / width) as usize];
+                !matches!(tile, TileType::Wall | TileType::Water | TileType::DeadZone)
+            })
+            .collect();
+
+        // Wall colliders are in tile units, anything they overlap is blocked even if the tile
+        // itself looks walkable
+        for collider in &map_layout.environmental_colliders {
+            let center = collider.transform.translation.xy();
+            let half_size = Vec2::new(collider.width, collider.height) / 2.0;
+            let min = (center - half_size).floor().as_ivec2();
+            let max = (center + half_size).ceil().as_ivec2();
+
+            for x in min.x.max(0)..max.x.min(width) {
+                for y in min.y.max(0)..max.y.min(height) {
+                    walkable[(y * width + x) as usize] = false;
+                }
+            }
+        }
+
+        Self {
+            width,
+            height,
+            walkable,
+            origin: world_config.tile_to_world(map_layout.size, IVec2::ZERO),
+            tile_size: Vec2::new(world_config.tile_size.x, world_config.tile_size.y),
+        }
+    }
+
+    fn index(&self, tile: IVec2) -> Option<usize> {
+        (tile.x >= 0 && tile.y >= 0 && tile.x < self.width && tile.y < self.height)
+            .then(|| (tile.y * self.width + tile.x) as usize)
+    }
+
+    pub fn tile_size(&self) -> Vec2 {
+        self.tile_size
+    }
+
+    pub fn world_to_tile(&self, position: Vec2) -> IVec2 {
+        ((position - self.origin) / self.tile_size)
+            .floor()
+            .as_ivec2()
+    }
+
+    pub fn tile_center(&self, tile: IVec2) -> Vec2 {
+        self.origin + (tile.as_vec2() + 0.5) * self.tile_size
+    }
+
+    pub fn is_walkable(&self, tile: IVec2) -> bool {
+        self.index(tile).is_some_and(|index| self.walkable[index])
+    }
+
+    /// Diagonal moves are only allowed when both tiles beside them are open, so paths never cut
+    /// through the corner of a wall
+    fn can_step(&self, from: IVec2, offset: IVec2) -> bool {
+        self.is_walkable(from + offset)
+            && (offset.x == 0
+                || offset.y == 0
+                || (self.is_walkable(from + IVec2::new(offset.x, 0))
+                    && self.is_walkable(from + IVec2::new(0, offset.y))))
+    }
+
+    /// Closest open tile to `tile`, for characters or goals that overlap a wall
+    fn nearest_walkable(&self, tile: IVec2) -> Option<IVec2> {
+        if self.is_walkable(tile) {
+            return Some(tile);
+        }
+
+        (1..=NEAREST_OPEN_SEARCH_RADIUS).find_map(|radius| {
+            (-radius..=radius)
+                .flat_map(|x| (-radius..=radius).map(move |y| IVec2::new(x, y)))
+                .filter(|offset| offset.x.abs() == radius || offset.y.abs() == radius)
+                .map(|offset| tile + offset)
+                .find(|candidate| self.is_walkable(*candidate))
+        })
+    }
+
+    /// Whether a character could walk in a straight line between two points without touching a
+    /// blocked tile
+    pub fn is_clear_line(&self, from: Vec2, to: Vec2) -> bool {
+        let offset = to - from;
+        let Ok(direction) = Dir2::new(offset) else {
+            return self.is_walkable(self.world_to_tile(from));
+        };
+
+        let side = direction.perp() * CHARACTER_CLEARANCE;
+        let step = self.tile_size.min_element() / 4.0;
+        let steps = (offset.length() / step).ceil() as i32;
+
+        (0..=steps).all(|i| {
+            let point = from + *direction * (i as f32 * step).min(offset.length());
+            [point, point + side, point - side]
+                .iter()
+                .all(|p| self.is_walkable(self.world_to_tile(*p)))
+        })
+    }
+
+    /// A* over the grid, returns the center of each tile along the way (excluding the start)
+    pub fn find_path(&self, from: Vec2, to: Vec2) -> Option<Vec<Vec2>> {
+        let start = self.nearest_walkable(self.world_to_tile(from))?;
+        let goal = self.nearest_walkable(self.world_to_tile(to))?;
+
+        if start == goal {
+            return Some(vec![to]);
+        }
+
+        let heuristic = |tile: IVec2| {
+            let delta = (goal - tile).abs();
+            STRAIGHT_COST * delta.max_element() as u32
+                + (DIAGONAL_COST - STRAIGHT_COST) * delta.min_element() as u32
+        };
+
+        let tile_count = (self.width * self.height) as usize;
+        let mut costs = vec![u32::MAX; tile_count];
+        let mut came_from: Vec<Option<IVec2>> = vec![None; tile_count];
+        let mut open = BinaryHeap::new();
+
+        costs[self.index(start)?] = 0;
+        open.push(Reverse((heuristic(start), start.x, start.y)));
+
+        let mut expanded = 0;
+        while let Some(Reverse((_, x, y))) = open.pop() {
+            let current = IVec2::new(x, y);
+            if current == goal {
+                break;
+            }
+
+            expanded += 1;
+            if expanded > MAX_SEARCH_NODES {
+                return None;
+            }
+
+            let current_cost = costs[self.index(current)?];
+
+            for (offset, step_cost) in NEIGHBORS {
+                if !self.can_step(current, offset) {
+                    continue;
+                }
+
+                let neighbor = current + offset;
+                let neighbor_index = self.index(neighbor)?;
+                let cost = current_cost + step_cost;
+
+                if cost < costs[neighbor_index] {
+                    costs[neighbor_index] = cost;
+                    came_from[neighbor_index] = Some(current);
+                    open.push(Reverse((
+                        cost + heuristic(neighbor),
+                        neighbor.x,
+                        neighbor.y,
+                    )));
+                }
+            }
+        }
+
+        came_from[self.index(goal)?]?;
+
+        let mut path = vec![to];
+        let mut current = goal;
+        while let Some(previous) = came_from[self.index(current)?] {
+            if previous == start {
+                break;
+            }
+            path.push(self.tile_center(previous));
+            current = previous;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Furthest point along the path that can be walked to in a straight line
+    fn furthest_visible(&self, from: Vec2, path: &[Vec2]) -> Option<Vec2> {
+        path.iter()
+            .rev()
+            .find(|waypoint| self.is_clear_line(from, **waypoint))
+            .or(path.first())
+            .copied()
+    }
+}
+
+/// Path a character is following around walls, kept between frames so it is only searched for
+/// again once the goal moves to a different tile
+#[derive(Component, Default)]
+pub struct NavPath {
+    goal_tile: Option<IVec2>,
+    waypoints: Vec<Vec2>,
+}
+
+impl NavPath {
+    fn clear(&mut self) {
+        self.goal_tile = None;
+        self.waypoints.clear();
+    }
+}
+
+/// Distance from every tile to the player, shared by every character chasing them so dozens of
+/// enemies don't each need their own path
+#[derive(Resource)]
+pub struct PlayerFlowField {
+    goal: IVec2,
+    costs: Vec<u32>,
+}
+
+impl PlayerFlowField {
+    fn new(grid: &NavGrid, goal: IVec2) -> Self {
+        let mut costs = vec![u32::MAX; (grid.width * grid.height) as usize];
+        let mut open = BinaryHeap::new();
+
+        if let Some(goal_index) = grid.index(goal) {
+            costs[goal_index] = 0;
+            open.push(Reverse((0, goal.x, goal.y)));
+        }
+
+        while let Some(Reverse((cost, x, y))) = open.pop() {
+            let current = IVec2::new(x, y);
+            if grid.index(current).is_none_or(|index| cost > costs[index]) {
+                continue;
+            }
+
+            for (offset, step_cost) in NEIGHBORS {
+                if !grid.can_step(current, offset) {
+                    continue;
+                }
+
+                let neighbor = current + offset;
+                let Some(neighbor_index) = grid.index(neighbor) else {
+                    continue;
+                };
+
+                let neighbor_cost = cost + step_cost;
+                if neighbor_cost < costs[neighbor_index] {
+                    costs[neighbor_index] = neighbor_cost;
+                    open.push(Reverse((neighbor_cost, neighbor.x, neighbor.y)));
+                }
+            }
+        }
+
+        Self { goal, costs }
+    }
+
+    /// Neighboring tile that gets closest to the player, if the tile is connected to them at all
+    pub fn next_tile(&self, grid: &NavGrid, tile: IVec2) -> Option<IVec2> {
+        let current_cost = self.costs[grid.index(tile)?];
+        if current_cost == u32::MAX || tile == self.goal {
+            return None;
+        }
+
+        NEIGHBORS
+            .iter()
+            .filter(|(offset, _)| grid.can_step(tile, *offset))
+            .map(|(offset, _)| tile + *offset)
+            .filter_map(|neighbor| Some((neighbor, self.costs[grid.index(neighbor)?])))
+            .filter(|(_, cost)| *cost < current_cost)
+            .min_by_key(|(_, cost)| *cost)
+            .map(|(neighbor, _)| neighbor)
+    }
+}
+
+/// Finds which way a character should move to get somewhere without walking into walls. Falls back
+/// to straight lines when there is no grid for the zone or no path can be found.
+#[derive(SystemParam)]
+pub struct Navigation<'w> {
+    grid: Option<Res<'w, NavGrid>>,
+    flow_field: Option<Res<'w, PlayerFlowField>>,
+}
+
+impl Navigation<'_> {
+    /// Direction to move from `from` to reach `to`. The path found is cached in `nav_path`, and
+    /// only searched for again when `to` moves to another tile.
+    pub fn direction_to(&self, from: Vec2, to: Vec2, nav_path: &mut NavPath) -> Vec2 {
+        let straight = (to - from).normalize_or_zero();
+
+        let Some(grid) = &self.grid else {
+            return straight;
+        };
+
+        if grid.is_clear_line(from, to) {
+            nav_path.clear();
+            return straight;
+        }
+
+        let Some(goal_tile) = grid.nearest_walkable(grid.world_to_tile(to)) else {
+            return straight;
+        };
+
+        if nav_path.goal_tile != Some(goal_tile) {
+            nav_path.goal_tile = Some(goal_tile);
+            // No path leaves the waypoints empty, we head straight for the goal until it moves
+            nav_path.waypoints = grid.find_path(from, to).unwrap_or_default();
+        }
+
+        // The goal may have moved within its tile
+        if let Some(last) = nav_path.waypoints.last_mut() {
+            *last = to;
+        }
+
+        let reached_distance = grid.tile_size.min_element() / 2.0;
+        let reached = nav_path
+            .waypoints
+            .iter()
+            .take_while(|waypoint| waypoint.distance(from) < reached_distance)
+            .count();
+        nav_path.waypoints.drain(..reached);
+
+        grid.furthest_visible(from, &nav_path.waypoints)
+            .map_or(straight, |waypoint| (waypoint - from).normalize_or_zero())
+    }
+
+    /// Direction to move from `from` to reach the player at `player_position`, using the shared
+    /// flow field instead of searching for a path
+    pub fn direction_to_player(
+        &self,
+        from: Vec2,
+        player_position: Vec2,
+        nav_path: &mut NavPath,
+    ) -> Vec2 {
+        let (Some(grid), Some(flow_field)) = (&self.grid, &self.flow_field) else {
+            return self.direction_to(from, player_position, nav_path);
+        };
+
+        if grid.is_clear_line(from, player_position) {
+            return (player_position - from).normalize_or_zero();
+        }
+
+        let tile = grid
+            .nearest_walkable(grid.world_to_tile(from))
+            .unwrap_or_else(|| grid.world_to_tile(from));
+
+        match flow_field.next_tile(grid, tile) {
+            Some(next_tile) => (grid.tile_center(next_tile) - from).normalize_or_zero(),
+            None => self.direction_to(from, player_position, nav_path),
+        }
+    }
+
+    /// Open direction closest to `desired`, ex. backing away from a target without backing into a
+    /// wall. Returns zero if every direction is blocked.
+    pub fn open_direction(&self, from: Vec2, desired: Vec2) -> Vec2 {
+        let Some(grid) = &self.grid else {
+            return desired;
+        };
+
+        let Ok(desired) = Dir2::new(desired) else {
+            return Vec2::ZERO;
+        };
+
+        let look_ahead = grid.tile_size.max_element();
+        let is_open = |direction: Vec2| grid.is_clear_line(from, from + direction * look_ahead);
+
+        if is_open(*desired) {
+            return *desired;
+        }
+
+        // Try directions at increasing angles away from the one we wanted, alternating sides
+        (1..=4)
+            .flat_map(|i| [i as f32, -(i as f32)])
+            .map(|i| desired.rotate(Vec2::from_angle(i * std::f32::consts::FRAC_PI_4)))
+            .find(|direction| is_open(*direction))
+            .unwrap_or(Vec2::ZERO)
+    }
+}
+
+fn build_nav_grid(
+    mut commands: Commands,
+    map_layout: Res<MapLayout>,
+    world_config: Res<WorldSpaceConfig>,
+) {
+    commands.remove_resource::<PlayerFlowField>();
+    commands.insert_resource(NavGrid::new(&map_layout, &world_config));
+}
+
+/// The flow field only needs rebuilding when the player steps onto a new tile
+fn update_player_flow_field(
+    mut commands: Commands,
+    grid: Option<Res<NavGrid>>,
+    flow_field: Option<Res<PlayerFlowField>>,
+    player: Single<&Transform, With<Player>>,
+) {
+    let Some(grid) = grid else {
+        return;
+    };
+
+    let Some(player_tile) = grid.nearest_walkable(grid.world_to_tile(player.translation.xy()))
+    else {
+        return;
+    };
+
+    if grid.is_changed() || flow_field.is_none_or(|flow_field| flow_field.goal != player_tile) {
+        commands.insert_resource(PlayerFlowField::new(&grid, player_tile));
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, sync::OnceLock};
+use std::{collections::HashMap, f32::consts::TAU, sync::OnceLock};
 
 use avian2d::prelude::{Collider, CollisionLayers, RigidBody};
 use bevy::prelude::*;
@@ -12,6 +12,16 @@ use crate::{
     },
 };
 
+/// How far from the player companions arrive in a new zone
+const COMPANION_ARRIVAL_DISTANCE: f32 = 40.0;
+/// Enemy types picked from at random for enemy and boss spawns
+const ENEMY_TYPES: [EnemyType; 4] = [
+    EnemyType::FireMage,
+    EnemyType::IceMage,
+    EnemyType::Warrior,
+    EnemyType::Bat,
+];
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         OnEnter(AppState::SpawnZone),
@@ -47,6 +57,22 @@ fn convert_tiles_to_world_positions(
         .collect()
 }
 
+/// Picks a random enemy type for each spawn position
+fn spawn_data(positions: Vec<Vec2>, boss: bool, elite_chance: f32) -> Vec<EnemySpawnData> {
+    let mut rng = rng();
+
+    positions
+        .into_iter()
+        .map(|position| EnemySpawnData {
+            position,
+            enemy_type: ENEMY_TYPES[rng.random_range(0..ENEMY_TYPES.len())].clone(),
+            boss,
+            elite_chance,
+            minion: false,
+        })
+        .collect()
+}
+
 fn spawn_zone_colliders(
     mut commands: Commands,
     map_layout: Res<MapLayout>,
@@ -115,6 +141,7 @@ fn spawn_zone_entities(
     map_layout: Res<MapLayout>,
     world_config: Res<WorldSpaceConfig>,
     player_query: Single<&mut Transform, With<Player>>,
+    mut companion_query: Query<&mut Transform, (With<Companion>, Without<Player>)>,
 ) {
     //TODO: Markers should all store an associated type
     //So maps can have a set of enemy types that they create markers for
@@ -131,19 +158,25 @@ fn spawn_zone_entities(
     if let Some(enemy_positions) = map_layout.markers.get_markers(MarkerType::EnemySpawns) {
         let spawn_positions =
             convert_tiles_to_world_positions(enemy_positions, &world_config, &map_layout);
-        let mut rng = rng();
-        let enemy_types = [EnemyType::FireMage, EnemyType::IceMage, EnemyType::Warrior];
-
-        let enemy_spawn_data_list = spawn_positions
-            .into_iter()
-            .map(|pos| EnemySpawnData {
-                position: pos,
-                enemy_type: enemy_types[rng.random_range(0..3)].clone(),
-            })
-            .collect();
 
         info!("spawning enemies");
-        commands.trigger(SpawnEnemies(enemy_spawn_data_list));
+        commands.trigger(SpawnEnemies(spawn_data(
+            spawn_positions,
+            false,
+            map_layout.elite_chance,
+        )));
+    }
+
+    if let Some(boss_positions) = map_layout.markers.get_markers(MarkerType::BossSpawns) {
+        let spawn_positions =
+            convert_tiles_to_world_positions(boss_positions, &world_config, &map_layout);
+
+        info!("spawning bosses");
+        commands.trigger(SpawnEnemies(spawn_data(
+            spawn_positions,
+            true,
+            map_layout.elite_chance,
+        )));
     }
 
     // Spawn chests
@@ -153,6 +186,13 @@ fn spawn_zone_entities(
         commands.trigger(SpawnChestsEvent(spawn_positions));
     }
 
+    // Spawn traps
+    if let Some(trap_positions) = map_layout.markers.get_markers(MarkerType::TrapSpawns) {
+        let spawn_positions =
+            convert_tiles_to_world_positions(trap_positions, &world_config, &map_layout);
+        commands.trigger(SpawnTrapsEvent(spawn_positions));
+    }
+
     // Spawn NPCs
     if let Some(npc_positions) = map_layout.markers.get_markers(MarkerType::NPCSpawns) {
         let spawn_positions =
@@ -173,6 +213,15 @@ fn spawn_zone_entities(
 
             player_transform.translation =
                 player_spawn_position.extend(player_transform.translation.z);
+
+            // Companions come through the portal right behind the player
+            let companion_count = companion_query.iter().count() as f32;
+            for (i, mut companion_transform) in companion_query.iter_mut().enumerate() {
+                let offset =
+                    Vec2::from_angle(TAU * i as f32 / companion_count) * COMPANION_ARRIVAL_DISTANCE;
+                companion_transform.translation =
+                    (player_spawn_position + offset).extend(companion_transform.translation.z);
+            }
         }
     } else {
         warn!("Player spawn marker not found in map layout.");
@@ -2,6 +2,7 @@ mod chest;
 mod gold;
 mod map;
 mod portal;
+mod trap;
 
 use bevy::prelude::*;
 
@@ -10,8 +11,15 @@ pub mod prelude {
     pub use super::gold::*;
     pub use super::map::prelude::*;
     pub use super::portal::*;
+    pub use super::trap::*;
 }
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((chest::plugin, gold::plugin, portal::plugin, map::plugin));
+    app.add_plugins((
+        chest::plugin,
+        gold::plugin,
+        portal::plugin,
+        trap::plugin,
+        map::plugin,
+    ));
 }
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(Update, trigger_traps.in_set(InGameSystems::Simulation))
+        .add_observer(on_spawn_traps_event);
+
+    app.add_observer(despawn_all::<CleanupZone, Trap>);
+}
+
+#[derive(Debug, Event)]
+pub struct SpawnTrapsEvent(pub Vec<Vec2>);
+
+/// Hidden vent in the floor that periodically erupts, spawning its `GroundEffects`
+#[derive(Component)]
+struct Trap {
+    eruption: Timer,
+}
+
+fn on_spawn_traps_event(
+    trap_spawn_trigger: On<SpawnTrapsEvent>,
+    mut commands: Commands,
+    sprites: Res<SpriteAssets>,
+    sprite_layouts: Res<SpriteSheetLayouts>,
+) {
+    for spawn_position in &trap_spawn_trigger.0 {
+        commands.spawn(fire_vent(&sprites, &sprite_layouts, *spawn_position));
+    }
+}
+
+fn fire_vent(
+    sprites: &SpriteAssets,
+    sprite_layouts: &SpriteSheetLayouts,
+    spawn_position: Vec2,
+) -> impl Bundle {
+    (
+        Name::new("Fire Vent"),
+        Trap {
+            eruption: Timer::from_seconds(6.0, TimerMode::Repeating),
+        },
+        Sprite::from_color(Color::srgba(0.15, 0.1, 0.1, 0.6), Vec2::splat(20.0)),
+        Transform::from_translation(spawn_position.extend(ZLayer::OnGround.z())),
+        related!(GroundEffects[fire_pool(sprites, sprite_layouts)]),
+    )
+}
+
+fn trigger_traps(
+    mut commands: Commands,
+    mut trap_query: Query<(Entity, &mut Trap, &Transform)>,
+    time: Res<Time>,
+) {
+    for (trap_entity, mut trap, transform) in &mut trap_query {
+        if trap.eruption.tick(time.delta()).just_finished() {
+            commands.trigger(SpawnGroundEffects {
+                entity: trap_entity,
+                position: transform.translation.truncate(),
+                faction: Faction::Environment,
+            });
+        }
+    }
+}